@@ -78,6 +78,7 @@
 #![cfg_attr(not(test), feature(generator_trait))]
 #![cfg_attr(not(stage0), feature(nll))]
 #![cfg_attr(not(stage0), feature(infer_outlives_requirements))]
+#![cfg_attr(not(stage0), allow(unstable_issue_zero))]
 #![cfg_attr(test, feature(test))]
 
 #![feature(allocator_api)]
@@ -18,6 +18,7 @@
 #![feature(linkage)]
 #![cfg_attr(not(stage0), feature(nll))]
 #![cfg_attr(not(stage0), feature(infer_outlives_requirements))]
+#![cfg_attr(not(stage0), allow(unstable_issue_zero))]
 #![feature(staged_api)]
 #![feature(rustc_attrs)]
 #![cfg_attr(dummy_jemalloc, allow(dead_code, unused_extern_crates))]
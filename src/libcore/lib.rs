@@ -93,6 +93,8 @@
 #![feature(never_type)]
 #![cfg_attr(not(stage0), feature(nll))]
 #![cfg_attr(not(stage0), feature(infer_outlives_requirements))]
+// FIXME: use `issue = "none"` and drop this once stage0 accepts it
+#![cfg_attr(not(stage0), allow(unstable_issue_zero))]
 #![feature(exhaustive_patterns)]
 #![feature(macro_at_most_once_rep)]
 #![feature(no_core)]
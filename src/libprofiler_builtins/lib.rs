@@ -17,4 +17,5 @@
 #![allow(unused_features)]
 #![cfg_attr(not(stage0), feature(nll))]
 #![cfg_attr(not(stage0), feature(infer_outlives_requirements))]
+#![cfg_attr(not(stage0), allow(unstable_issue_zero))]
 #![feature(staged_api)]
@@ -125,7 +125,7 @@ impl_stable_hash_for!(enum ::rustc_target::spec::abi::Abi {
     Unadjusted
 });
 
-impl_stable_hash_for!(struct ::syntax::attr::Deprecation { since, note });
+impl_stable_hash_for!(struct ::syntax::attr::Deprecation { since, note, replacement });
 impl_stable_hash_for!(struct ::syntax::attr::Stability {
     level,
     feature,
@@ -338,6 +338,24 @@ declare_lint! {
      cannot be referred to by absolute paths"
 }
 
+declare_lint! {
+    pub PRE_1_0_STABILITY_VERSION,
+    Warn,
+    "detects `#[stable]` attributes whose `since` version predates Rust 1.0.0"
+}
+
+declare_lint! {
+    pub UNSTABLE_TYPE_IN_STABLE_ALIAS,
+    Warn,
+    "detects stable type aliases that refer to unstable types"
+}
+
+declare_lint! {
+    pub UNSTABLE_ISSUE_ZERO,
+    Warn,
+    "detects `#[unstable]` attributes that use `issue = \"0\"` instead of `issue = \"none\"`"
+}
+
 /// Some lints that are buffered from `libsyntax`. See `syntax::early_buffered_lints`.
 pub mod parser {
     declare_lint! {
@@ -406,6 +424,9 @@ impl LintPass for HardwiredLints {
             PROC_MACRO_DERIVE_RESOLUTION_FALLBACK,
             MACRO_USE_EXTERN_CRATE,
             MACRO_EXPANDED_MACRO_EXPORTS_ACCESSED_BY_ABSOLUTE_PATHS,
+            PRE_1_0_STABILITY_VERSION,
+            UNSTABLE_TYPE_IN_STABLE_ALIAS,
+            UNSTABLE_ISSUE_ZERO,
             parser::QUESTION_MARK_MACRO_SEP,
         )
     }
@@ -255,8 +255,8 @@ macro_rules! return_if_err {
     ($inp: expr) => (
         match $inp {
             Ok(v) => v,
-            Err(()) => {
-                debug!("mc reported err");
+            Err(err) => {
+                debug!("mc reported err: {:?}", err);
                 return
             }
         }
@@ -437,7 +437,7 @@ impl<'a, 'gcx, 'tcx> ExprUseVisitor<'a, 'gcx, 'tcx> {
             }
 
             hir::ExprKind::Match(ref discr, ref arms, _) => {
-                let discr_cmt = Rc::new(return_if_err!(self.mc.cat_expr(&discr)));
+                let discr_cmt = Rc::new(return_if_err!(self.mc.cat_match_scrutinee(expr)));
                 let r = self.tcx().types.re_empty;
                 self.borrow_expr(&discr, r, ty::ImmBorrow, MatchDiscriminant);
 
@@ -541,7 +541,9 @@ impl<'a, 'gcx, 'tcx> ExprUseVisitor<'a, 'gcx, 'tcx> {
             }
 
             hir::ExprKind::Yield(ref value) => {
-                self.consume_expr(&value);
+                let cmt = return_if_err!(self.mc.cat_yielded(expr));
+                self.delegate_consume(value.id, value.span, &cmt);
+                self.walk_expr(&value);
             }
         }
     }
@@ -666,13 +668,13 @@ impl<'a, 'gcx, 'tcx> ExprUseVisitor<'a, 'gcx, 'tcx> {
                         self.tcx().field_index(f.id, self.mc.tables) == f_index
                     });
                     if !is_mentioned {
-                        let cmt_field = self.mc.cat_field(
+                        let cmt_field = return_if_err!(self.mc.cat_field(
                             &*with_expr,
                             with_cmt.clone(),
                             f_index,
                             with_field.ident,
                             with_field.ty(self.tcx(), substs)
-                        );
+                        ));
                         self.delegate_consume(with_expr.id, with_expr.span, &cmt_field);
                     }
                 }
@@ -816,7 +818,7 @@ impl<'a, 'gcx, 'tcx> ExprUseVisitor<'a, 'gcx, 'tcx> {
                                mode: &mut TrackMatchMode) {
         debug!("determine_pat_move_mode cmt_discr={:?} pat={:?}", cmt_discr,
                pat);
-        return_if_err!(self.mc.cat_pattern(cmt_discr, pat, |cmt_pat, pat| {
+        return_if_err!(self.mc.cat_pattern(cmt_discr, pat, mc::BindingFirst, |cmt_pat, pat| {
             if let PatKind::Binding(..) = pat.node {
                 let bm = *self.mc.tables.pat_binding_modes().get(pat.hir_id)
                                                           .expect("missing binding mode");
@@ -842,7 +844,7 @@ impl<'a, 'gcx, 'tcx> ExprUseVisitor<'a, 'gcx, 'tcx> {
 
         let tcx = self.tcx();
         let ExprUseVisitor { ref mc, ref mut delegate, param_env } = *self;
-        return_if_err!(mc.cat_pattern(cmt_discr.clone(), pat, |cmt_pat, pat| {
+        return_if_err!(mc.cat_pattern(cmt_discr.clone(), pat, mc::BindingFirst, |cmt_pat, pat| {
             if let PatKind::Binding(_, canonical_id, ..) = pat.node {
                 debug!(
                     "walk_pat: binding cmt_pat={:?} pat={:?} match_mode={:?}",
@@ -888,7 +890,7 @@ impl<'a, 'gcx, 'tcx> ExprUseVisitor<'a, 'gcx, 'tcx> {
         // the interior nodes (enum variants and structs), as opposed
         // to the above loop's visit of than the bindings that form
         // the leaves of the pattern tree structure.
-        return_if_err!(mc.cat_pattern(cmt_discr, pat, |cmt_pat, pat| {
+        return_if_err!(mc.cat_pattern(cmt_discr, pat, mc::BindingFirst, |cmt_pat, pat| {
             let qpath = match pat.node {
                 PatKind::Path(ref qpath) |
                 PatKind::TupleStruct(ref qpath, ..) |
@@ -954,7 +956,7 @@ impl<'a, 'gcx, 'tcx> ExprUseVisitor<'a, 'gcx, 'tcx> {
                         closure_hir_id: hir::HirId,
                         closure_span: Span,
                         upvar: &hir::Freevar)
-                        -> mc::McResult<mc::cmt_<'tcx>> {
+                        -> mc::McResult<'tcx, mc::cmt_<'tcx>> {
         // Create the cmt for the variable being borrowed, from the
         // caller's perspective
         let var_hir_id = self.tcx().hir.node_to_hir_id(upvar.var_id());
@@ -48,6 +48,8 @@ impl LibFeatures {
 pub struct LibFeatureCollector<'a, 'tcx: 'a> {
     tcx: TyCtxt<'a, 'tcx, 'tcx>,
     lib_features: LibFeatures,
+    // The first attribute declaring each stable feature.
+    stable_spans: FxHashMap<Symbol, Span>,
 }
 
 impl<'a, 'tcx> LibFeatureCollector<'a, 'tcx> {
@@ -55,6 +57,7 @@ impl<'a, 'tcx> LibFeatureCollector<'a, 'tcx> {
         LibFeatureCollector {
             tcx,
             lib_features: LibFeatures::new(),
+            stable_spans: FxHashMap(),
         }
     }
 
@@ -113,13 +116,18 @@ impl<'a, 'tcx> LibFeatureCollector<'a, 'tcx> {
                             since,
                             prev_since,
                         );
-                        self.tcx.sess.struct_span_err_with_code(span, &msg,
-                            DiagnosticId::Error("E0711".into())).emit();
+                        let mut err = self.tcx.sess.struct_span_err_with_code(span, &msg,
+                            DiagnosticId::Error("E0711".into()));
+                        if let Some(&prev_span) = self.stable_spans.get(&feature) {
+                            err.span_label(prev_span, "previously declared stable here");
+                        }
+                        err.emit();
                         return;
                     }
                 }
 
                 self.lib_features.stable.insert(feature, since);
+                self.stable_spans.entry(feature).or_insert(span);
             }
             (None, false, _) => {
                 self.lib_features.unstable.insert(feature);
@@ -65,16 +65,21 @@ pub use self::InteriorKind::*;
 pub use self::MutabilityCategory::*;
 pub use self::AliasableReason::*;
 pub use self::Note::*;
+pub use self::PatternWalkOrder::*;
 
 use self::Aliasability::*;
 
 use middle::region;
+use mir;
+use session::DiagnosticMessageId;
 use hir::def_id::{DefId, LocalDefId};
 use hir::map as hir_map;
 use infer::InferCtxt;
 use hir::def::{Def, CtorKind};
 use ty::adjustment;
+use ty::cast::CastKind;
 use ty::{self, Ty, TyCtxt};
+use ty::subst::Substs;
 use ty::fold::TypeFoldable;
 
 use hir::{MutImmutable, MutMutable, PatKind};
@@ -83,13 +88,15 @@ use hir;
 use syntax::ast::{self, Name};
 use syntax_pos::Span;
 
+use std::cell::RefCell;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ptr;
 use rustc_data_structures::sync::Lrc;
 use std::rc::Rc;
-use util::nodemap::ItemLocalSet;
+use util::nodemap::{FxHashMap, ItemLocalSet};
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub enum Categorization<'tcx> {
     Rvalue(ty::Region<'tcx>),              // temporary val, argument is its scope
     StaticItem,
@@ -97,20 +104,156 @@ pub enum Categorization<'tcx> {
     Local(ast::NodeId),                    // local variable
     Deref(cmt<'tcx>, PointerKind<'tcx>), // deref of a ptr
     Interior(cmt<'tcx>, InteriorKind),     // something interior: field, tuple, etc
-    Downcast(cmt<'tcx>, DefId),            // selects a particular enum variant (*1)
+    Downcast(cmt<'tcx>, DefId, Span),      // selects a particular enum variant (*1)
+    PtrCast(cmt<'tcx>, Ty<'tcx>, ty::Region<'tcx>), // no-op raw pointer cast, region is its scope
 
-    // (*1) downcast is only required if the enum has more than one variant
+    // (*1) downcast is only required if the enum has more than one variant;
+    //      the span records where the discriminant being matched on appears
+}
+
+impl<'tcx> Categorization<'tcx> {
+    /// Returns the source name of the local variable this categorizes,
+    /// e.g. "`x`". The `Debug` output only includes the `NodeId`, so that
+    /// it can be used without a `TyCtxt` in scope.
+    pub fn describe_local(&self, tcx: TyCtxt) -> Option<String> {
+        match *self {
+            Categorization::Local(id) => Some(format!("`{}`", tcx.hir.name(id))),
+            _ => None,
+        }
+    }
+
+    /// Returns a short, stable name for the kind of this categorization,
+    /// for tools consuming the compiler's JSON output. Unlike
+    /// `cmt_::descriptive_string`, it is not meant to be read as English
+    /// and does not need a `TyCtxt`.
+    pub fn describe_kind(&self) -> &'static str {
+        match *self {
+            Categorization::Rvalue(..) => "rvalue",
+            Categorization::StaticItem => "static",
+            Categorization::Upvar(..) => "upvar",
+            Categorization::Local(..) => "local",
+            Categorization::Deref(..) => "deref",
+            Categorization::Interior(_, InteriorField(_)) |
+            Categorization::Interior(_, InteriorUnionField(_)) => "field",
+            Categorization::Interior(_, InteriorElement(_)) => "index",
+            Categorization::Downcast(..) => "downcast",
+            Categorization::PtrCast(..) => "ptr_cast",
+        }
+    }
+
+    /// Returns the path this categorizes as it would be written in the
+    /// source, e.g. `self.field.subfield` or `*x`, for use in error
+    /// messages. Derefs that autoderef would insert before a field access
+    /// or an index are omitted.
+    pub fn display(&self, tcx: TyCtxt) -> String {
+        display_place(tcx, self)
+    }
+}
+
+impl<'tcx> DisplayPlace for Categorization<'tcx> {
+    fn display_step(&self, tcx: TyCtxt) -> DisplayStep<Self> {
+        match *self {
+            Categorization::Rvalue(..) => DisplayStep::Root("<temporary>".to_string()),
+            Categorization::StaticItem => DisplayStep::Root("<static item>".to_string()),
+            Categorization::Local(id) => DisplayStep::Root(tcx.hir.name(id).to_string()),
+            Categorization::Upvar(ref upvar) => {
+                let var_id = tcx.hir.hir_to_node_id(upvar.id.var_id);
+                DisplayStep::Root(tcx.hir.name(var_id).to_string())
+            }
+            Categorization::Deref(ref base, _) => DisplayStep::Deref(&base.cat),
+            Categorization::Interior(ref base, InteriorField(FieldIndex(_, name))) |
+            Categorization::Interior(ref base, InteriorUnionField(FieldIndex(_, name))) => {
+                DisplayStep::Field(&base.cat, name)
+            }
+            Categorization::Interior(ref base, InteriorElement(..)) => {
+                DisplayStep::Index(&base.cat)
+            }
+            Categorization::Downcast(ref base, variant_did, _) => {
+                DisplayStep::Downcast(&base.cat, variant_did)
+            }
+            Categorization::PtrCast(ref base, ..) => DisplayStep::Skip(&base.cat),
+        }
+    }
+}
+
+/// The outermost step of a place, as rendered by `display_place`.
+pub enum DisplayStep<'a, P: ?Sized + 'a> {
+    /// The place is a root, e.g. a local variable, with the given name.
+    Root(String),
+    /// A step that is not written in the source, e.g. a pointer cast.
+    Skip(&'a P),
+    Deref(&'a P),
+    Field(&'a P, Name),
+    Index(&'a P),
+    Downcast(&'a P, DefId),
+}
+
+/// A place that can be rendered by `display_place`, e.g. a
+/// `Categorization` or a borrowck loan path.
+pub trait DisplayPlace {
+    fn display_step(&self, tcx: TyCtxt) -> DisplayStep<Self>;
+}
+
+/// Returns `place` as it would be written in the source, e.g.
+/// `self.field.subfield` or `*x`, for use in error messages. Derefs that
+/// autoderef would insert before a field access or an index are omitted.
+pub fn display_place<P: DisplayPlace + ?Sized>(tcx: TyCtxt, place: &P) -> String {
+    let mut out = String::new();
+    append_place(tcx, place, false, &mut out);
+    out
+}
+
+fn append_place<P: DisplayPlace + ?Sized>(tcx: TyCtxt,
+                                          place: &P,
+                                          autoderefd: bool,
+                                          out: &mut String) {
+    match place.display_step(tcx) {
+        DisplayStep::Root(name) => out.push_str(&name),
+        DisplayStep::Skip(base) => append_place(tcx, base, autoderefd, out),
+        DisplayStep::Deref(base) => {
+            // For a path like `(*x).f` or `(*x)[3]`, autoderef rules would
+            // normally allow users to omit the `*x`, so just render such
+            // paths as `x.f` or `x[..]` respectively.
+            if !autoderefd {
+                out.push('*');
+            }
+            append_place(tcx, base, autoderefd, out);
+        }
+        DisplayStep::Field(base, name) => {
+            append_place(tcx, base, true, out);
+            out.push('.');
+            out.push_str(&name.as_str());
+        }
+        DisplayStep::Index(base) => {
+            append_place(tcx, base, true, out);
+            out.push_str("[..]");
+        }
+        DisplayStep::Downcast(base, variant_did) => {
+            out.push('(');
+            append_place(tcx, base, autoderefd, out);
+            out.push_str(" as ");
+            out.push_str(&tcx.item_path_str(variant_did));
+            out.push(')');
+        }
+    }
 }
 
 // Represents any kind of upvar
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct Upvar {
     pub id: ty::UpvarId,
     pub kind: ty::ClosureKind
 }
 
+impl Upvar {
+    /// Returns the `DefId` of the closure that captured this upvar.
+    pub fn closure_def_id(&self) -> DefId {
+        self.id.closure_expr_id.to_def_id()
+    }
+}
+
 // different kinds of pointers:
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
 pub enum PointerKind<'tcx> {
     /// `Box<T>`
     Unique,
@@ -124,15 +267,26 @@ pub enum PointerKind<'tcx> {
 
 // We use the term "interior" to mean "something reachable from the
 // base without a pointer dereference", e.g. a field
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
 pub enum InteriorKind {
     InteriorField(FieldIndex),
+    InteriorUnionField(FieldIndex), // Reading it is unsafe
     InteriorElement(InteriorOffsetKind),
 }
 
+impl InteriorKind {
+    /// The interior kind for the field `field` of a value of type `ty`.
+    pub fn of_field(ty: Ty, field: FieldIndex) -> InteriorKind {
+        match ty.sty {
+            ty::Adt(adt_def, _) if adt_def.is_union() => InteriorUnionField(field),
+            _ => InteriorField(field),
+        }
+    }
+}
+
 // Contains index of a field that is actually used for loan path comparisons and
 // string representation of the field that should be used only for diagnostics.
-#[derive(Clone, Copy, Eq)]
+#[derive(Clone, Copy, Eq, RustcEncodable, RustcDecodable)]
 pub struct FieldIndex(pub usize, pub Name);
 
 impl PartialEq for FieldIndex {
@@ -147,13 +301,26 @@ impl Hash for FieldIndex {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+impl FieldIndex {
+    /// The name to show for this field in diagnostics: the name from its
+    /// definition when known, and otherwise the name recorded when the
+    /// field was categorized (the index, for tuple fields).
+    pub fn display_name(&self, adt_field: Option<&ty::FieldDef>) -> String {
+        match adt_field {
+            Some(field_def) => field_def.ident.to_string(),
+            None => self.1.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, RustcEncodable, RustcDecodable)]
 pub enum InteriorOffsetKind {
-    Index,            // e.g. `array_expr[index_expr]`
+    ConstIndex(u64),  // e.g. `array_expr[3]`
+    RuntimeIndex,     // e.g. `array_expr[index_expr]`
     Pattern,          // e.g. `fn foo([_, a, _, _]: [A; 4]) { ... }`
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, RustcEncodable, RustcDecodable)]
 pub enum MutabilityCategory {
     McImmutable, // Immutable.
     McDeclared,  // Directly declared as mutable.
@@ -165,14 +332,102 @@ pub enum MutabilityCategory {
 // Upvar categorization can generate a variable number of nested
 // derefs.  The note allows detecting them without deep pattern
 // matching on the categorization.
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub enum Note {
+#[derive(Clone, Copy, PartialEq, Debug, RustcEncodable, RustcDecodable)]
+pub enum Note<'tcx> {
     NoteClosureEnv(ty::UpvarId), // Deref through closure env
     NoteUpvarRef(ty::UpvarId),   // Deref through by-ref upvar
     NoteIndex,                   // Deref as part of desugaring `x[]` into its two components
+    NoteYield,                   // Place yielded out of a generator
+    NoteTry,                     // Scrutinee of the match a `?` desugars into
+    // Overloaded deref through the given `Deref(Mut)::deref(_mut)` trait method and substs
+    NoteDeref(DefId, &'tcx Substs<'tcx>),
+    NoteConstPromotion,          // Rvalue promoted to a constant with a `'static` lifetime
+    NoteMatchGuard,              // Place bound by the pattern of a match arm with a guard
     NoteNone                     // Nothing special
 }
 
+impl<'tcx> Note<'tcx> {
+    /// Returns an adapter that displays this note like its `Debug` output,
+    /// except that the upvar notes name the captured variable and the
+    /// location of the closure capturing it, and `NoteDeref` names the
+    /// method of the impl by path if it is known, e.g.
+    /// "NoteUpvarRef(`x`, closure defined at src/main.rs:3:13)".
+    pub fn note_with_tcx(&self, tcx: TyCtxt<'_, '_, 'tcx>) -> impl fmt::Display + 'tcx {
+        let detail = match *self {
+            NoteClosureEnv(upvar_id) | NoteUpvarRef(upvar_id) => {
+                let var_name = tcx.hir.name(tcx.hir.hir_to_node_id(upvar_id.var_id));
+                let closure_span = tcx.hir.span_if_local(upvar_id.closure_expr_id.to_def_id())
+                    .expect("closure of an upvar is not local");
+                let loc = tcx.sess.source_map().lookup_char_pos(closure_span.lo());
+                Some(format!("`{}`, closure defined at {}:{}:{}",
+                             var_name, loc.file.name, loc.line, loc.col.to_usize() + 1))
+            }
+            NoteDeref(method_def_id, _) => {
+                let impl_method = self.overloaded_deref_impl(tcx).and_then(|impl_did| {
+                    let name = tcx.item_name(method_def_id);
+                    tcx.associated_items(impl_did).find(|item| {
+                        item.kind == ty::AssociatedKind::Method && item.ident.name == name
+                    })
+                });
+                Some(tcx.item_path_str(impl_method.map_or(method_def_id, |item| item.def_id)))
+            }
+            _ => None,
+        };
+        NoteWithTcx { note: *self, detail }
+    }
+
+    /// Returns the type an overloaded deref noted with `NoteDeref` is
+    /// applied to, e.g. `Rc<T>` or `ManuallyDrop<T>`.
+    pub fn overloaded_deref_self_ty(&self) -> Option<Ty<'tcx>> {
+        match *self {
+            NoteDeref(_, substs) => Some(substs.type_at(0)),
+            _ => None,
+        }
+    }
+
+    /// Returns the impl of `Deref` or `DerefMut` used by an overloaded
+    /// deref noted with `NoteDeref`, if it can be told apart from the other
+    /// impls of that trait. Only meant for error reporting, as looking it
+    /// up goes through all the impls of the trait that may apply.
+    pub fn overloaded_deref_impl(&self, tcx: TyCtxt<'_, '_, 'tcx>) -> Option<DefId> {
+        let (method_def_id, self_ty) = match *self {
+            NoteDeref(method_def_id, substs) => (method_def_id, substs.type_at(0)),
+            _ => return None,
+        };
+        let trait_did = tcx.trait_of_item(method_def_id)?;
+        let mut candidates = vec![];
+        tcx.for_each_relevant_impl(trait_did, self_ty, |impl_did| {
+            candidates.push(impl_did);
+        });
+        if candidates.len() == 1 {
+            candidates.pop()
+        } else {
+            None
+        }
+    }
+}
+
+struct NoteWithTcx<'tcx> {
+    note: Note<'tcx>,
+    /// What to show in place of the `Debug` output of the note's payload.
+    detail: Option<String>,
+}
+
+impl<'tcx> fmt::Display for NoteWithTcx<'tcx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.note {
+            NoteClosureEnv(_) => "NoteClosureEnv",
+            NoteUpvarRef(_) => "NoteUpvarRef",
+            NoteDeref(..) => "NoteDeref",
+            note => return write!(f, "{:?}", note),
+        };
+        match self.detail {
+            Some(ref detail) => write!(f, "{}({})", name, detail),
+            None => write!(f, "{:?}", self.note),
+        }
+    }
+}
+
 // `cmt`: "Category, Mutability, and Type".
 //
 // a complete categorization of a value indicating where it originated
@@ -191,23 +446,129 @@ pub enum Note {
 // again a dereference, but its type is the type *before* the
 // dereference (`@T`). So use `cmt.ty` to find the type of the value in
 // a consistent fashion. For more details, see the method `cat_pattern`
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, RustcEncodable, RustcDecodable)]
 pub struct cmt_<'tcx> {
     pub hir_id: hir::HirId,        // HIR id of expr/pat producing this value
     pub span: Span,                // span of same expr/pat
     pub cat: Categorization<'tcx>, // categorization of expr
     pub mutbl: MutabilityCategory, // mutability of expr as place
     pub ty: Ty<'tcx>,              // type of the expr (*see WARNING above*)
-    pub note: Note,                // Note about the provenance of this cmt
+    pub note: Note<'tcx>,          // Note about the provenance of this cmt
 }
 
 pub type cmt<'tcx> = Rc<cmt_<'tcx>>;
 
+/// The region a temporary lives for, as computed by
+/// `MemCategorizationContext::temporary_scope`. Keeping it distinct from
+/// other regions avoids passing an unrelated one to `cat_rvalue`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemporaryScope<'tcx>(ty::Region<'tcx>);
+
+impl<'tcx> From<TemporaryScope<'tcx>> for ty::Region<'tcx> {
+    fn from(scope: TemporaryScope<'tcx>) -> ty::Region<'tcx> {
+        scope.0
+    }
+}
+
+/// A plain-data snapshot of a `cmt_` and its bases, used for
+/// `-Z dump-mem-categorization`. See `cmt_::to_debug_tree`.
+#[derive(Clone, Debug)]
+pub struct DebugNode {
+    pub kind: String,
+    pub mutbl: String,
+    pub ty: String,
+    pub span: String,
+    pub children: Vec<DebugNode>,
+}
+
+impl DebugNode {
+    /// Renders the tree with one node per line, each base indented one
+    /// level deeper than the place built on top of it.
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out, 0);
+        out
+    }
+
+    fn render_into(&self, out: &mut String, depth: usize) {
+        for _ in 0..depth {
+            out.push_str("  ");
+        }
+        out.push_str(&format!("{} ({}) : {} @ {}\n", self.kind, self.mutbl, self.ty, self.span));
+        for child in &self.children {
+            child.render_into(out, depth + 1);
+        }
+    }
+}
+
 pub enum ImmutabilityBlame<'tcx> {
     ImmLocal(ast::NodeId),
     ClosureEnv(LocalDefId),
     LocalDeref(ast::NodeId),
-    AdtFieldDeref(&'tcx ty::AdtDef, &'tcx ty::FieldDef)
+    AdtFieldDeref(&'tcx ty::AdtDef, &'tcx ty::FieldDef),
+    /// The immutable reference is element `usize` of a tuple held in the
+    /// local variable `ast::NodeId`.
+    TupleFieldDeref(ast::NodeId, usize),
+    /// The place is element `usize` of a tuple held in the immutable local
+    /// variable `ast::NodeId`.
+    ImmTupleField(ast::NodeId, usize),
+}
+
+impl<'tcx> ImmutabilityBlame<'tcx> {
+    /// Returns the position at which `mut` should be inserted to fix the
+    /// immutability, when it is blamed on a local binding. For `ImmLocal`,
+    /// `ImmTupleField` and a `LocalDeref` of a `ref` binding, this is just
+    /// before the binding's name (`mut x`, `ref mut x`); for any other
+    /// `LocalDeref`, it is just after the `&` of the binding's declared type.
+    pub fn suggestion_span(&self, tcx: TyCtxt) -> Option<Span> {
+        let (node_id, is_deref) = match *self {
+            ImmutabilityBlame::ImmLocal(node_id) |
+            ImmutabilityBlame::ImmTupleField(node_id, _) => (node_id, false),
+            ImmutabilityBlame::LocalDeref(node_id) => (node_id, true),
+            ImmutabilityBlame::ClosureEnv(_) |
+            ImmutabilityBlame::AdtFieldDeref(..) |
+            ImmutabilityBlame::TupleFieldDeref(..) => return None,
+        };
+        let (annotation, ident) = match tcx.hir.find(node_id) {
+            Some(hir_map::NodeBinding(&hir::Pat {
+                node: PatKind::Binding(annotation, _, ident, _), ..
+            })) => (annotation, ident),
+            _ => return None,
+        };
+        match annotation {
+            hir::BindingAnnotation::Unannotated if !is_deref => Some(ident.span.shrink_to_lo()),
+            hir::BindingAnnotation::Ref if is_deref => Some(ident.span.shrink_to_lo()),
+            hir::BindingAnnotation::Unannotated if is_deref => {
+                match declared_ty(tcx, node_id).map(|ty| &ty.node) {
+                    Some(&hir::TyKind::Rptr(_, hir::MutTy { ref ty, mutbl: MutImmutable })) => {
+                        Some(ty.span.shrink_to_lo())
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the type annotation of the local binding `node_id`, if it is
+/// the whole pattern of a `let` or of an explicit function argument.
+fn declared_ty<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
+                               node_id: ast::NodeId)
+                               -> Option<&'gcx hir::Ty> {
+    let parent = tcx.hir.get_parent_node(node_id);
+    match tcx.hir.get(parent) {
+        hir_map::NodeLocal(local) if local.pat.id == node_id => local.ty.as_ref().map(|ty| &**ty),
+        node => {
+            let fn_like = hir_map::blocks::FnLikeNode::from_node(node)?;
+            let body = tcx.hir.body(fn_like.body());
+            let index = body.arguments.iter().position(|arg| arg.pat.id == node_id)?;
+            if index == 0 && fn_like.decl().has_implicit_self {
+                return None;
+            }
+            fn_like.decl().inputs.get(index)
+        }
+    }
 }
 
 impl<'tcx> cmt_<'tcx> {
@@ -220,7 +581,7 @@ impl<'tcx> cmt_<'tcx> {
             _ =>  bug!("interior cmt {:?} is not an ADT", self)
         };
         let variant_def = match self.cat {
-            Categorization::Downcast(_, variant_did) => {
+            Categorization::Downcast(_, variant_did, _) => {
                 adt_def.variant_with_id(variant_did)
             }
             _ => {
@@ -238,10 +599,16 @@ impl<'tcx> cmt_<'tcx> {
                 match base_cmt.cat {
                     Categorization::Local(node_id) =>
                         Some(ImmutabilityBlame::LocalDeref(node_id)),
-                    Categorization::Interior(ref base_cmt, InteriorField(field_index)) => {
-                        base_cmt.resolve_field(field_index.0).map(|(adt_def, field_def)| {
-                            ImmutabilityBlame::AdtFieldDeref(adt_def, field_def)
-                        })
+                    Categorization::Interior(ref base_cmt, InteriorField(field_index)) |
+                    Categorization::Interior(ref base_cmt, InteriorUnionField(field_index)) => {
+                        match (&base_cmt.ty.sty, &base_cmt.cat) {
+                            (ty::Tuple(..), &Categorization::Local(node_id)) => {
+                                Some(ImmutabilityBlame::TupleFieldDeref(node_id, field_index.0))
+                            }
+                            _ => base_cmt.resolve_field(field_index.0).map(|(adt_def, field_def)| {
+                                ImmutabilityBlame::AdtFieldDeref(adt_def, field_def)
+                            }),
+                        }
                     }
                     Categorization::Upvar(Upvar { id, .. }) => {
                         if let NoteClosureEnv(..) = self.note {
@@ -258,12 +625,21 @@ impl<'tcx> cmt_<'tcx> {
             }
             Categorization::Rvalue(..) |
             Categorization::Upvar(..) |
+            Categorization::PtrCast(..) |
             Categorization::Deref(_, UnsafePtr(..)) => {
                 // This should not be reachable up to inference limitations.
                 None
             }
+            Categorization::Interior(ref base_cmt, InteriorField(field_index)) => {
+                match (&base_cmt.ty.sty, &base_cmt.cat) {
+                    (ty::Tuple(..), &Categorization::Local(node_id)) => {
+                        Some(ImmutabilityBlame::ImmTupleField(node_id, field_index.0))
+                    }
+                    _ => base_cmt.immutability_blame(),
+                }
+            }
             Categorization::Interior(ref base_cmt, _) |
-            Categorization::Downcast(ref base_cmt, _) |
+            Categorization::Downcast(ref base_cmt, _, _) |
             Categorization::Deref(ref base_cmt, _) => {
                 base_cmt.immutability_blame()
             }
@@ -290,16 +666,94 @@ impl HirNode for hir::Pat {
     fn span(&self) -> Span { self.span }
 }
 
-#[derive(Clone)]
 pub struct MemCategorizationContext<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
     pub tcx: TyCtxt<'a, 'gcx, 'tcx>,
     pub region_scope_tree: &'a region::ScopeTree,
     pub tables: &'a ty::TypeckTables<'tcx>,
     rvalue_promotable_map: Option<Lrc<ItemLocalSet>>,
     infcx: Option<&'a InferCtxt<'a, 'gcx, 'tcx>>,
+    /// Memoized results of `cat_expr`, keyed by the expression's id and
+    /// the number of its adjustments that were applied. Only used once
+    /// typeck is complete, i.e. when `infcx` is `None`.
+    cmt_cache: RefCell<FxHashMap<(ast::NodeId, usize), cmt<'tcx>>>,
+}
+
+/// The reason the categorization of an expression or pattern failed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum McError<'tcx> {
+    /// A deref (explicit, implicit, or in a pattern) of a type that
+    /// cannot be dereferenced.
+    NonDerefable(Ty<'tcx>),
+    /// A slice pattern matched against a type that cannot be indexed.
+    NonIndexable(Ty<'tcx>),
+    /// The assigned place of a compound assignment is not mutable.
+    ImmutableAssignee(Ty<'tcx>),
+    /// An error has already been reported for this body.
+    TaintedByErrors,
+    /// The type of the node is not (yet) known.
+    NoType(ast::NodeId),
+    /// A field access to a field, given by its index, that the variant
+    /// of the base type being accessed does not have.
+    NoSuchField(Ty<'tcx>, usize),
+}
+
+pub type McResult<'tcx, T> = Result<T, McError<'tcx>>;
+
+/// How a method call takes its receiver, see `cat_method_receiver`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceiverMode {
+    /// The receiver is moved (or copied) into the call.
+    ByValue,
+    /// The receiver is auto-referenced with the given mutability.
+    ByRef(hir::Mutability),
+}
+
+/// How the inline assembly uses one of its operands, see
+/// `cat_inline_asm_operands`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsmOperandKind {
+    /// The operand is only read.
+    Read,
+    /// The operand is a place that is only written.
+    Write,
+    /// The operand is a place that is both read and written.
+    ReadWrite,
+}
+
+/// When `cat_pattern` invokes its callback for a binding with a
+/// sub-pattern, such as `x @ Some(_)`, relative to the sub-pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternWalkOrder {
+    /// The binding is visited before its sub-pattern.
+    BindingFirst,
+    /// The sub-pattern is visited before the binding.
+    SubPatternFirst,
 }
 
-pub type McResult<T> = Result<T, ()>;
+/// The root of a place built by `cat_place_from_mir_like_path`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathRoot {
+    /// A local variable or argument.
+    Local(ast::NodeId),
+    /// A variable captured by a closure, seen from inside its body.
+    Upvar(ty::UpvarId),
+    /// A `static` item.
+    Static(DefId),
+}
+
+/// A projection applied by `cat_place_from_mir_like_path`, mirroring
+/// MIR's `ProjectionElem`. Types are those of the resulting place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathElem<'tcx> {
+    /// The field with the given index of a struct, union, tuple or
+    /// enum variant (after a `Downcast`).
+    Field(usize, Ty<'tcx>),
+    Deref,
+    /// An element of an array or slice, at a runtime index.
+    Index(Ty<'tcx>),
+    /// The enum variant with the given `DefId`.
+    Downcast(DefId),
+}
 
 impl MutabilityCategory {
     pub fn from_mutbl(m: hir::Mutability) -> MutabilityCategory {
@@ -392,12 +846,32 @@ impl MutabilityCategory {
         ret
     }
 
+    /// Returns true if adding `mut` to a declaration may make this place
+    /// mutable. Places that are `McInherited` get their mutability from
+    /// an owner further up, so a local `mut` is never the fix for them.
+    pub fn requires_mut_keyword(&self) -> bool {
+        match *self {
+            McImmutable => true,
+            McDeclared | McInherited => false,
+        }
+    }
+
     pub fn to_user_str(&self) -> &'static str {
         match *self {
             McDeclared | McInherited => "mutable",
             McImmutable => "immutable",
         }
     }
+
+    /// Like `to_user_str`, but tells declared and inherited mutability
+    /// apart.
+    pub fn to_user_str_verbose(&self) -> &'static str {
+        match *self {
+            McDeclared => "declared mutable",
+            McInherited => "inherited mutable",
+            McImmutable => "immutable",
+        }
+    }
 }
 
 impl<'a, 'tcx> MemCategorizationContext<'a, 'tcx, 'tcx> {
@@ -411,7 +885,63 @@ impl<'a, 'tcx> MemCategorizationContext<'a, 'tcx, 'tcx> {
             region_scope_tree,
             tables,
             rvalue_promotable_map,
-            infcx: None
+            infcx: None,
+            cmt_cache: RefCell::new(FxHashMap()),
+        }
+    }
+
+    /// Like `new`, but fails up front with `McError::TaintedByErrors` if
+    /// `tables` belong to a body in which type errors were reported.
+    /// Prefer this over `new` when the caller would otherwise have to
+    /// cope with categorization bailing out on every expression with a
+    /// missing or erroneous type, e.g. in lints that may run on bodies
+    /// that did not type-check.
+    pub fn try_new(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                   region_scope_tree: &'a region::ScopeTree,
+                   tables: &'a ty::TypeckTables<'tcx>,
+                   rvalue_promotable_map: Option<Lrc<ItemLocalSet>>)
+                   -> McResult<'tcx, MemCategorizationContext<'a, 'tcx, 'tcx>> {
+        if tables.tainted_by_errors {
+            return Err(McError::TaintedByErrors);
+        }
+        Ok(MemCategorizationContext::new(tcx, region_scope_tree, tables, rvalue_promotable_map))
+    }
+
+    /// Builds a context for the body owned by `def_id` out of its cached
+    /// typeck tables, region scope tree and rvalue promotability map, and
+    /// hands it to `f`. This lets lint passes call `cat_expr` and friends
+    /// without threading those tables through themselves. The scope tree
+    /// is owned by this call, hence the closure rather than a returned
+    /// context. Like `try_new`, this bails out on bodies with type errors.
+    pub fn for_lint<F, R>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId, f: F) -> McResult<'tcx, R>
+        where F: for<'b> FnOnce(&MemCategorizationContext<'b, 'tcx, 'tcx>) -> R
+    {
+        let tables = tcx.typeck_tables_of(def_id);
+        let region_scope_tree = tcx.region_scope_tree(def_id);
+        let rvalue_promotable_map = tcx.rvalue_promotable_map(def_id);
+        let mc = MemCategorizationContext::try_new(tcx,
+                                                   &region_scope_tree,
+                                                   tables,
+                                                   Some(rvalue_promotable_map))?;
+        Ok(f(&mc))
+    }
+
+    /// Returns a copy of this context that starts with an empty cache.
+    /// This may not be called on a context created with `with_infer`: its
+    /// in-progress tables hold inference variables that only its inference
+    /// context can resolve, and the results of categorizing them must not
+    /// outlive it.
+    pub fn without_infcx(&self) -> MemCategorizationContext<'a, 'tcx, 'tcx> {
+        if self.infcx.is_some() {
+            bug!("copying a MemCategorizationContext created during type inference");
+        }
+        MemCategorizationContext {
+            tcx: self.tcx,
+            region_scope_tree: self.region_scope_tree,
+            tables: self.tables,
+            rvalue_promotable_map: self.rvalue_promotable_map.clone(),
+            infcx: None,
+            cmt_cache: RefCell::new(FxHashMap()),
         }
     }
 }
@@ -426,12 +956,23 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
     ///   temporaries may be overly conservative;
     /// - similarly, as the results of upvar analysis are not yet
     ///   known, the results around upvar accesses may be incorrect.
+    ///
+    /// `tables` must be the in-progress tables of `infcx`, since the
+    /// inference variables in them only mean something to the inference
+    /// context that created them. This is checked in debug builds.
     pub fn with_infer(infcx: &'a InferCtxt<'a, 'gcx, 'tcx>,
                       region_scope_tree: &'a region::ScopeTree,
                       tables: &'a ty::TypeckTables<'tcx>)
                       -> MemCategorizationContext<'a, 'gcx, 'tcx> {
         let tcx = infcx.tcx;
 
+        debug_assert!(infcx.in_progress_tables.map_or(false, |in_progress| {
+            in_progress.try_borrow().ok().map_or(false, |in_progress| {
+                ptr::eq(&*in_progress, tables)
+            })
+        }), "MemCategorizationContext::with_infer: `tables` are not the in-progress tables \
+             of `infcx`");
+
         // Subtle: we can't do rvalue promotion analysis until the
         // typeck phase is complete, which means that you can't trust
         // the rvalue lifetimes that result, but that's ok, since we
@@ -444,6 +985,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             tables,
             rvalue_promotable_map,
             infcx: Some(infcx),
+            cmt_cache: RefCell::new(FxHashMap()),
         }
     }
 
@@ -475,19 +1017,22 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
     fn resolve_type_vars_or_error(&self,
                                   id: hir::HirId,
                                   ty: Option<Ty<'tcx>>)
-                                  -> McResult<Ty<'tcx>> {
+                                  -> McResult<'tcx, Ty<'tcx>> {
         match ty {
             Some(ty) => {
                 let ty = self.resolve_type_vars_if_possible(&ty);
-                if ty.references_error() || ty.is_ty_var() {
+                if ty.references_error() {
                     debug!("resolve_type_vars_or_error: error from {:?}", ty);
-                    Err(())
+                    Err(McError::TaintedByErrors)
+                } else if ty.is_ty_var() {
+                    debug!("resolve_type_vars_or_error: unresolved {:?}", ty);
+                    Err(McError::NoType(self.tcx.hir.hir_to_node_id(id)))
                 } else {
                     Ok(ty)
                 }
             }
             // FIXME
-            None if self.is_tainted_by_errors() => Err(()),
+            None if self.is_tainted_by_errors() => Err(McError::TaintedByErrors),
             None => {
                 let id = self.tcx.hir.hir_to_node_id(id);
                 bug!("no type for node {}: {} in mem_categorization",
@@ -498,16 +1043,16 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
 
     pub fn node_ty(&self,
                    hir_id: hir::HirId)
-                   -> McResult<Ty<'tcx>> {
+                   -> McResult<'tcx, Ty<'tcx>> {
         self.resolve_type_vars_or_error(hir_id,
                                         self.tables.node_id_to_type_opt(hir_id))
     }
 
-    pub fn expr_ty(&self, expr: &hir::Expr) -> McResult<Ty<'tcx>> {
+    pub fn expr_ty(&self, expr: &hir::Expr) -> McResult<'tcx, Ty<'tcx>> {
         self.resolve_type_vars_or_error(expr.hir_id, self.tables.expr_ty_opt(expr))
     }
 
-    pub fn expr_ty_adjusted(&self, expr: &hir::Expr) -> McResult<Ty<'tcx>> {
+    pub fn expr_ty_adjusted(&self, expr: &hir::Expr) -> McResult<'tcx, Ty<'tcx>> {
         self.resolve_type_vars_or_error(expr.hir_id, self.tables.expr_ty_adjusted_opt(expr))
     }
 
@@ -521,7 +1066,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
     ///   implicit deref patterns attached (e.g., it is really
     ///   `&Some(x)`). In that case, we return the "outermost" type
     ///   (e.g., `&Option<T>).
-    pub fn pat_ty_adjusted(&self, pat: &hir::Pat) -> McResult<Ty<'tcx>> {
+    pub fn pat_ty_adjusted(&self, pat: &hir::Pat) -> McResult<'tcx, Ty<'tcx>> {
         // Check for implicit `&` types wrapping the pattern; note
         // that these are never attached to binding patterns, so
         // actually this is somewhat "disjoint" from the code below
@@ -538,7 +1083,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
 
 
     /// Like `pat_ty`, but ignores implicit `&` patterns.
-    fn pat_ty_unadjusted(&self, pat: &hir::Pat) -> McResult<Ty<'tcx>> {
+    fn pat_ty_unadjusted(&self, pat: &hir::Pat) -> McResult<'tcx, Ty<'tcx>> {
         let base_ty = self.node_ty(pat.hir_id)?;
         debug!("pat_ty(pat={:?}) base_ty={:?}", pat, base_ty);
 
@@ -559,7 +1104,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                         Some(t) => t.ty,
                         None => {
                             debug!("By-ref binding of non-derefable type {:?}", base_ty);
-                            return Err(());
+                            return Err(McError::NonDerefable(base_ty));
                         }
                     }
                 } else {
@@ -573,52 +1118,112 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         Ok(ret_ty)
     }
 
-    pub fn cat_expr(&self, expr: &hir::Expr) -> McResult<cmt_<'tcx>> {
+    pub fn cat_expr(&self, expr: &hir::Expr) -> McResult<'tcx, cmt_<'tcx>> {
         // This recursion helper avoids going through *too many*
         // adjustments, since *only* non-overloaded deref recurses.
         fn helper<'a, 'gcx, 'tcx>(mc: &MemCategorizationContext<'a, 'gcx, 'tcx>,
                                   expr: &hir::Expr,
                                   adjustments: &[adjustment::Adjustment<'tcx>])
-                                   -> McResult<cmt_<'tcx>> {
+                                   -> McResult<'tcx, cmt_<'tcx>> {
             match adjustments.split_last() {
                 None => mc.cat_expr_unadjusted(expr),
                 Some((adjustment, previous)) => {
-                    mc.cat_expr_adjusted_with(expr, || helper(mc, expr, previous), adjustment)
+                    mc.memoize(expr.id, adjustments.len(), || {
+                        let source = previous.last().map(|previous| previous.target);
+                        mc.cat_expr_adjusted_with(expr, source, || helper(mc, expr, previous),
+                                                  adjustment)
+                    })
                 }
             }
         }
 
-        helper(self, expr, self.tables.expr_adjustments(expr))
+        let ret = helper(self, expr, self.tables.expr_adjustments(expr));
+        if self.tcx.sess.opts.debugging_opts.dump_mem_categorization {
+            if let Ok(ref cmt) = ret {
+                self.dump_cmt(expr, cmt);
+            }
+        }
+        ret
+    }
+
+    /// Emits the `-Z dump-mem-categorization` note for `expr`. Regionck,
+    /// rvalue promotion and borrowck all categorize the same expressions, so
+    /// only the first categorization of each expression is reported.
+    fn dump_cmt(&self, expr: &hir::Expr, cmt: &cmt_<'tcx>) {
+        let key = (DiagnosticMessageId::MemCategorizationDump(expr.id), None, String::new());
+        if !self.tcx.sess.one_time_diagnostics.borrow_mut().insert(key) {
+            return;
+        }
+        self.tcx.sess.diagnostic()
+            .span_note_diag(expr.span, "memory categorization of this expression")
+            .note(&cmt.to_debug_tree(self.tcx).render_tree())
+            .emit();
+    }
+
+    /// Like `cat_expr`, but also returns the `cmt` produced at each step of
+    /// applying the expression's adjustments. The intermediate `cmt`s are
+    /// ordered outermost-first: the one just before the final adjustment
+    /// comes first. As in `cat_expr`, only built-in derefs look at what
+    /// they are applied to, so the steps before the last adjustment that
+    /// is not one are never categorized and are not returned; the
+    /// unadjusted expression comes last only if all adjustments are
+    /// built-in derefs.
+    pub fn cat_place_expr(&self, expr: &hir::Expr)
+                          -> McResult<'tcx, (cmt<'tcx>, Vec<cmt<'tcx>>)> {
+        let cmt = Rc::new(self.cat_expr(expr)?);
+        let mut intermediates = vec![];
+        let mut current = cmt.clone();
+        for adjustment in self.tables.expr_adjustments(expr).iter().rev() {
+            let base = match (&adjustment.kind, &current.cat) {
+                (&adjustment::Adjust::Deref(None), &Categorization::Deref(ref base, _)) => {
+                    base.clone()
+                }
+                _ => break,
+            };
+            intermediates.push(base.clone());
+            current = base;
+        }
+        Ok((cmt, intermediates))
     }
 
     pub fn cat_expr_adjusted(&self, expr: &hir::Expr,
                              previous: cmt_<'tcx>,
                              adjustment: &adjustment::Adjustment<'tcx>)
-                             -> McResult<cmt_<'tcx>> {
-        self.cat_expr_adjusted_with(expr, || Ok(previous), adjustment)
+                             -> McResult<'tcx, cmt_<'tcx>> {
+        self.cat_expr_adjusted_with(expr, Some(previous.ty), || Ok(previous), adjustment)
     }
 
+    /// Categorizes `expr` with `adjustment` applied on top of `previous`.
+    /// `source` is the type `adjustment` is applied to, or `None` if it is
+    /// the first adjustment of `expr`; it is only needed to record the
+    /// method of an overloaded deref.
     fn cat_expr_adjusted_with<F>(&self, expr: &hir::Expr,
+                                 source: Option<Ty<'tcx>>,
                                  previous: F,
                                  adjustment: &adjustment::Adjustment<'tcx>)
-                                 -> McResult<cmt_<'tcx>>
-        where F: FnOnce() -> McResult<cmt_<'tcx>>
+                                 -> McResult<'tcx, cmt_<'tcx>>
+        where F: FnOnce() -> McResult<'tcx, cmt_<'tcx>>
     {
         debug!("cat_expr_adjusted_with({:?}): {:?}", adjustment, expr);
         let target = self.resolve_type_vars_if_possible(&adjustment.target);
         match adjustment.kind {
             adjustment::Adjust::Deref(overloaded) => {
                 // Equivalent to *expr or something similar.
-                let base = Rc::new(if let Some(deref) = overloaded {
+                if let Some(deref) = overloaded {
                     let ref_ty = self.tcx.mk_ref(deref.region, ty::TypeAndMut {
                         ty: target,
                         mutbl: deref.mutbl,
                     });
-                    self.cat_rvalue_node(expr.hir_id, expr.span, ref_ty)
+                    let base = Rc::new(self.cat_rvalue_node(expr.hir_id, expr.span, ref_ty));
+                    let source = match source {
+                        Some(source) => self.resolve_type_vars_if_possible(&source),
+                        None => self.expr_ty(expr)?,
+                    };
+                    let (method_def_id, substs) = deref.method_call(self.tcx, source);
+                    self.cat_deref(expr, base, NoteDeref(method_def_id, substs))
                 } else {
-                    previous()?
-                });
-                self.cat_deref(expr, base, NoteNone)
+                    self.cat_deref(expr, Rc::new(previous()?), NoteNone)
+                }
             }
 
             adjustment::Adjust::NeverToAny |
@@ -634,7 +1239,32 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         }
     }
 
-    pub fn cat_expr_unadjusted(&self, expr: &hir::Expr) -> McResult<cmt_<'tcx>> {
+    /// Looks up the categorization of `id` with its first `adjustments`
+    /// adjustments applied in the cache, computing and recording it with
+    /// `f` on a miss. Errors are never cached. While type inference is
+    /// still in progress the cache is bypassed, as the types it would
+    /// capture may not be fully resolved yet.
+    fn memoize<F>(&self, id: ast::NodeId, adjustments: usize, f: F) -> McResult<'tcx, cmt_<'tcx>>
+        where F: FnOnce() -> McResult<'tcx, cmt_<'tcx>>
+    {
+        if self.infcx.is_some() {
+            return f();
+        }
+
+        let key = (id, adjustments);
+        if let Some(cmt) = self.cmt_cache.borrow().get(&key) {
+            return Ok((**cmt).clone());
+        }
+        let cmt = f()?;
+        self.cmt_cache.borrow_mut().insert(key, Rc::new(cmt.clone()));
+        Ok(cmt)
+    }
+
+    pub fn cat_expr_unadjusted(&self, expr: &hir::Expr) -> McResult<'tcx, cmt_<'tcx>> {
+        self.memoize(expr.id, 0, || self.cat_expr_unadjusted_uncached(expr))
+    }
+
+    fn cat_expr_unadjusted_uncached(&self, expr: &hir::Expr) -> McResult<'tcx, cmt_<'tcx>> {
         debug!("cat_expr: id={} expr={:?}", expr.id, expr);
 
         let expr_ty = self.expr_ty(expr)?;
@@ -655,10 +1285,10 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                    expr,
                    base_cmt);
             let f_index = self.tcx.field_index(expr.id, self.tables);
-            Ok(self.cat_field(expr, base_cmt, f_index, f_ident, expr_ty))
+            self.cat_field(expr, base_cmt, f_index, f_ident, expr_ty)
           }
 
-          hir::ExprKind::Index(ref base, _) => {
+          hir::ExprKind::Index(ref base, ref index) => {
             if self.tables.is_method_call(expr) {
                 // If this is an index implemented by a method call, then it
                 // will include an implicit deref of the result.
@@ -668,7 +1298,8 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                 self.cat_overloaded_place(expr, base, NoteIndex)
             } else {
                 let base_cmt = Rc::new(self.cat_expr(&base)?);
-                self.cat_index(expr, base_cmt, expr_ty, InteriorOffsetKind::Index)
+                let context = self.index_offset_kind(index);
+                self.cat_slice_elem(expr, base_cmt, expr_ty, context)
             }
           }
 
@@ -681,11 +1312,23 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             self.cat_expr(&e)
           }
 
+          hir::ExprKind::Cast(ref base, _) => {
+            self.cat_cast(expr, base, expr_ty)
+          }
+
+          hir::ExprKind::Yield(..) => {
+            // The value of a `yield` expression is whatever the generator
+            // is resumed with; the operand is categorized by `cat_yielded`.
+            // Returning the operand's place here would make a `yield`
+            // whose value is consumed move the operand a second time.
+            Ok(self.cat_rvalue_node(expr.hir_id, expr.span, expr_ty))
+          }
+
           hir::ExprKind::AddrOf(..) | hir::ExprKind::Call(..) |
           hir::ExprKind::Assign(..) | hir::ExprKind::AssignOp(..) |
           hir::ExprKind::Closure(..) | hir::ExprKind::Ret(..) |
-          hir::ExprKind::Unary(..) | hir::ExprKind::Yield(..) |
-          hir::ExprKind::MethodCall(..) | hir::ExprKind::Cast(..) |
+          hir::ExprKind::Unary(..) |
+          hir::ExprKind::MethodCall(..) |
           hir::ExprKind::Array(..) | hir::ExprKind::Tup(..) | hir::ExprKind::If(..) |
           hir::ExprKind::Binary(..) | hir::ExprKind::While(..) |
           hir::ExprKind::Block(..) | hir::ExprKind::Loop(..) | hir::ExprKind::Match(..) |
@@ -697,16 +1340,186 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         }
     }
 
+    /// Categorizes the operand of the `yield` expression `yield_expr`.
+    /// Places are marked with `NoteYield` (unless they already carry a
+    /// more specific note), so that later passes can recognize values
+    /// that cross a yield point; rvalues are returned as they are.
+    pub fn cat_yielded(&self, yield_expr: &hir::Expr) -> McResult<'tcx, cmt_<'tcx>> {
+        let value = match yield_expr.node {
+            hir::ExprKind::Yield(ref value) => value,
+            _ => span_bug!(yield_expr.span, "cat_yielded: not a yield expression"),
+        };
+        let mut cmt = self.cat_expr(value)?;
+        match cmt.cat {
+            Categorization::Rvalue(..) => {}
+            _ => {
+                if cmt.note == NoteNone {
+                    cmt.note = NoteYield;
+                }
+            }
+        }
+        debug!("cat_yielded ret {:?}", cmt);
+        Ok(cmt)
+    }
+
+    /// Categorizes the operands of the inline assembly expression
+    /// `asm_expr`: its outputs, followed by its inputs. The expression
+    /// itself is an rvalue, but its direct outputs are places written
+    /// (and, for `+` outputs, also read) by the assembly. Indirect outputs
+    /// are pointers to the written memory, so they are only read.
+    pub fn cat_inline_asm_operands(&self, asm_expr: &hir::Expr)
+                                   -> McResult<'tcx, Vec<(cmt<'tcx>, AsmOperandKind)>> {
+        let (ia, outputs, inputs) = match asm_expr.node {
+            hir::ExprKind::InlineAsm(ref ia, ref outputs, ref inputs) => (ia, outputs, inputs),
+            _ => span_bug!(asm_expr.span, "cat_inline_asm_operands: not an inline asm expression"),
+        };
+        let mut operands = Vec::with_capacity(outputs.len() + inputs.len());
+        for (o, output) in ia.outputs.iter().zip(outputs) {
+            let kind = if o.is_indirect {
+                AsmOperandKind::Read
+            } else if o.is_rw {
+                AsmOperandKind::ReadWrite
+            } else {
+                AsmOperandKind::Write
+            };
+            operands.push((Rc::new(self.cat_expr(output)?), kind));
+        }
+        for input in inputs {
+            operands.push((Rc::new(self.cat_expr(input)?), AsmOperandKind::Read));
+        }
+        debug!("cat_inline_asm_operands ret {:?}", operands);
+        Ok(operands)
+    }
+
+    /// Categorizes the scrutinee of the `match` expression `match_expr`.
+    /// When the match is the desugaring of `e?`, the scrutinee is the
+    /// `Try::into_result(e)` temporary, which is marked with `NoteTry`
+    /// so that later passes can attribute it to the `?`.
+    pub fn cat_match_scrutinee(&self, match_expr: &hir::Expr) -> McResult<'tcx, cmt_<'tcx>> {
+        let (discr, source) = match match_expr.node {
+            hir::ExprKind::Match(ref discr, _, source) => (discr, source),
+            _ => span_bug!(match_expr.span, "cat_match_scrutinee: not a match expression"),
+        };
+        let mut cmt = self.cat_expr(discr)?;
+        if source == hir::MatchSource::TryDesugar && cmt.note == NoteNone {
+            cmt.note = NoteTry;
+        }
+        debug!("cat_match_scrutinee ret {:?}", cmt);
+        Ok(cmt)
+    }
+
+    /// Categorizes the place assigned to by the compound assignment
+    /// `assign_expr` (`lhs op= rhs`), failing with
+    /// `McError::ImmutableAssignee` if it is not mutable. The only
+    /// adjustment of `lhs` is the `&mut` autoref of an overloaded
+    /// operator, so the unadjusted place is the one being assigned.
+    pub fn cat_assign_op_lhs(&self, assign_expr: &hir::Expr) -> McResult<'tcx, cmt_<'tcx>> {
+        let lhs = match assign_expr.node {
+            hir::ExprKind::AssignOp(_, ref lhs, _) => lhs,
+            _ => span_bug!(assign_expr.span, "cat_assign_op_lhs: not a compound assignment"),
+        };
+        let cmt = self.cat_expr_unadjusted(lhs)?;
+        if !cmt.mutbl.is_mutable() {
+            return Err(McError::ImmutableAssignee(cmt.ty));
+        }
+        debug!("cat_assign_op_lhs ret {:?}", cmt);
+        Ok(cmt)
+    }
+
+    /// Categorizes the receiver of the method call `call_expr` and reports
+    /// whether the call takes it by value or borrows it. For a borrowed
+    /// receiver, the returned cmt is the place that is auto-referenced,
+    /// i.e. the receiver after any autoderefs but before the autoref.
+    pub fn cat_method_receiver(&self, call_expr: &hir::Expr)
+                               -> McResult<'tcx, (cmt_<'tcx>, ReceiverMode)> {
+        let receiver = match call_expr.node {
+            hir::ExprKind::MethodCall(_, _, ref args) => &args[0],
+            _ => span_bug!(call_expr.span, "cat_method_receiver: not a method call"),
+        };
+        let adjustments = self.tables.expr_adjustments(receiver);
+        let autoref = adjustments.iter().position(|adjustment| match adjustment.kind {
+            adjustment::Adjust::Borrow(adjustment::AutoBorrow::Ref(..)) => true,
+            _ => false,
+        });
+        let (place_adjustments, mode) = match autoref {
+            Some(i) => {
+                let mutbl = match adjustments[i].kind {
+                    adjustment::Adjust::Borrow(adjustment::AutoBorrow::Ref(_, m)) => m.into(),
+                    _ => bug!(),
+                };
+                (&adjustments[..i], ReceiverMode::ByRef(mutbl))
+            }
+            None => (adjustments, ReceiverMode::ByValue),
+        };
+
+        let mut cmt = self.cat_expr_unadjusted(receiver)?;
+        for adjustment in place_adjustments {
+            cmt = self.cat_expr_adjusted(receiver, cmt, adjustment)?;
+        }
+        debug!("cat_method_receiver ret {:?} {:?}", cmt, mode);
+        Ok((cmt, mode))
+    }
+
+    /// Categorizes a cast expression. A no-op cast of a raw pointer to
+    /// another raw pointer type keeps the place being cast, so that
+    /// provenance analyses can see through it; any other cast, or a cast
+    /// of a temporary, is an rvalue.
+    fn cat_cast(&self,
+                expr: &hir::Expr,
+                base: &hir::Expr,
+                expr_ty: Ty<'tcx>)
+                -> McResult<'tcx, cmt_<'tcx>> {
+        let is_ptr_ptr_cast = match self.tables.cast_kinds().get(expr.hir_id) {
+            Some(&CastKind::PtrPtrCast) | Some(&CastKind::CoercionCast) => {
+                match (&self.expr_ty_adjusted(base)?.sty, &expr_ty.sty) {
+                    (&ty::RawPtr(_), &ty::RawPtr(_)) => true,
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+        if !is_ptr_ptr_cast {
+            return Ok(self.cat_rvalue_node(expr.hir_id, expr.span, expr_ty));
+        }
+
+        let base_cmt = Rc::new(self.cat_expr(base)?);
+        if let Categorization::Rvalue(..) = base_cmt.cat {
+            return Ok(self.cat_rvalue_node(expr.hir_id, expr.span, expr_ty));
+        }
+        // The result of the cast lives in a temporary of its own, which
+        // may be promoted like any other rvalue.
+        let (scope, promotable) = self.rvalue_scope(expr.hir_id, expr_ty);
+        let ret = cmt_ {
+            hir_id: expr.hir_id,
+            span: expr.span,
+            mutbl: base_cmt.mutbl,
+            cat: Categorization::PtrCast(base_cmt, expr_ty, scope),
+            ty: expr_ty,
+            note: if promotable { NoteConstPromotion } else { NoteNone },
+        };
+        debug!("cat_cast ret {:?}", ret);
+        Ok(ret)
+    }
+
     pub fn cat_def(&self,
                    hir_id: hir::HirId,
                    span: Span,
                    expr_ty: Ty<'tcx>,
                    def: Def)
-                   -> McResult<cmt_<'tcx>> {
+                   -> McResult<'tcx, cmt_<'tcx>> {
         debug!("cat_def: id={:?} expr={:?} def={:?}",
                hir_id, expr_ty, def);
 
         match def {
+          Def::StructCtor(_, CtorKind::Const) | Def::VariantCtor(_, CtorKind::Const) => {
+                // A unit struct or variant evaluates to a fresh value each
+                // time, not to a place shared between uses like a static:
+                // it can be moved out of and mutably borrowed like any
+                // other temporary. If it is promotable, `cat_rvalue_node`
+                // already gives it a `'static` region.
+                Ok(self.cat_rvalue_node(hir_id, span, expr_ty))
+          }
+
           Def::StructCtor(..) | Def::VariantCtor(..) | Def::Const(..) |
           Def::AssociatedConst(..) | Def::Fn(..) | Def::Method(..) => {
                 Ok(self.cat_rvalue_node(hir_id, span, expr_ty))
@@ -755,10 +1568,8 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                  span: Span,
                  var_id: ast::NodeId,
                  fn_node_id: ast::NodeId)
-                 -> McResult<cmt_<'tcx>>
+                 -> McResult<'tcx, cmt_<'tcx>>
     {
-        let fn_hir_id = self.tcx.hir.node_to_hir_id(fn_node_id);
-
         // An upvar can have up to 3 components. We translate first to a
         // `Categorization::Upvar`, which is itself a fiction -- it represents the reference to the
         // field from the environment.
@@ -781,8 +1592,89 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         // Fn             | copied -> &'env      | upvar -> &'env -> &'up bk
         // FnMut          | copied -> &'env mut  | upvar -> &'env mut -> &'up bk
         // FnOnce         | copied               | upvar -> &'up bk
+        //
+        // Generators (and so `async` blocks) follow the `FnOnce` row.
+        //
+        // `cat_captured_var` builds the first and last components; here
+        // we anchor them at the use and slot the env deref in between.
+
+        let closure_def_id = self.tcx.hir.local_def_id(fn_node_id);
+        let upvar_id = ty::UpvarId {
+            var_id: self.tcx.hir.node_to_hir_id(var_id),
+            closure_expr_id: closure_def_id.to_local(),
+        };
+        let upvar_capture = self.tables.upvar_capture(upvar_id);
+        let captured = self.cat_captured_var(closure_def_id, var_id, upvar_capture)?;
+
+        let (upvar, by_ref) = match captured.cat {
+            Categorization::Deref(ref upvar, ptr) => ((**upvar).clone(), Some(ptr)),
+            _ => ((*captured).clone(), None),
+        };
+        let kind = match upvar.cat {
+            Categorization::Upvar(Upvar { kind, .. }) => kind,
+            _ => span_bug!(span, "captured variable is not an upvar: {:?}", upvar),
+        };
+        let var_mutbl = upvar.mutbl;
+        let cmt_result = cmt_ { hir_id, span, ..upvar };
+
+        // If this is a `FnMut` or `Fn` closure, then the above is
+        // conceptually a `&mut` or `&` reference, so we have to add a
+        // deref.
+        let cmt_result = match kind {
+            ty::ClosureKind::FnOnce => {
+                cmt_result
+            }
+            ty::ClosureKind::FnMut => {
+                self.env_deref(hir_id, span, upvar_id, var_mutbl, ty::MutBorrow, cmt_result)
+            }
+            ty::ClosureKind::Fn => {
+                self.env_deref(hir_id, span, upvar_id, var_mutbl, ty::ImmBorrow, cmt_result)
+            }
+        };
+
+        // If this is a by-ref capture, redo the implicit deref of the
+        // reference we loaded on top of the env deref.
+        let cmt_result = match by_ref {
+            None => cmt_result,
+            Some(ptr) => cmt_ {
+                hir_id,
+                span,
+                cat: Categorization::Deref(Rc::new(cmt_result), ptr),
+                mutbl: captured.mutbl,
+                ty: captured.ty,
+                note: captured.note,
+            },
+        };
+
+        let ret = cmt_result;
+        debug!("cat_upvar ret={:?}", ret);
+        Ok(ret)
+    }
+
+    /// Categorizes the variable `var_id` as captured by the closure
+    /// `closure_def_id` with `capture`: the upvar itself, plus a deref of
+    /// the captured reference for by-ref captures. Unlike the cmt of a
+    /// use of the variable inside the closure body, this does not include
+    /// the deref of the closure's environment pointer, and it is anchored
+    /// at the variable's definition.
+    pub fn cat_captured_var(&self,
+                            closure_def_id: DefId,
+                            var_id: ast::NodeId,
+                            capture: ty::UpvarCapture<'tcx>)
+                            -> McResult<'tcx, cmt<'tcx>>
+    {
+        let fn_node_id = self.tcx.hir.as_local_node_id(closure_def_id)
+            .expect("captured variable of a non-local closure");
+        let fn_hir_id = self.tcx.hir.node_to_hir_id(fn_node_id);
+        let var_hir_id = self.tcx.hir.node_to_hir_id(var_id);
+        let span = self.tcx.hir.span(var_id);
 
         let kind = match self.node_ty(fn_hir_id)?.sty {
+            // Generators, including the ones `async` blocks and functions
+            // lower to, hold their upvars directly, so there is no
+            // environment pointer to deref. Their captures are still
+            // inferred like a closure's though, so a by-ref capture gets
+            // the deref of the captured reference below like any other.
             ty::Generator(..) => ty::ClosureKind::FnOnce,
             ty::Closure(closure_def_id, closure_substs) => {
                 match self.infcx {
@@ -802,11 +1694,9 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             ref t => span_bug!(span, "unexpected type for fn in mem_categorization: {:?}", t),
         };
 
-        let closure_expr_def_id = self.tcx.hir.local_def_id(fn_node_id);
-        let var_hir_id = self.tcx.hir.node_to_hir_id(var_id);
         let upvar_id = ty::UpvarId {
             var_id: var_hir_id,
-            closure_expr_id: closure_expr_def_id.to_local(),
+            closure_expr_id: closure_def_id.to_local(),
         };
 
         let var_ty = self.node_ty(var_hir_id)?;
@@ -817,8 +1707,8 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         // Construct the upvar. This represents access to the field
         // from the environment (perhaps we should eventually desugar
         // this field further, but it will do for now).
-        let cmt_result = cmt_ {
-            hir_id,
+        let upvar = cmt_ {
+            hir_id: var_hir_id,
             span,
             cat: Categorization::Upvar(Upvar {id: upvar_id, kind: kind}),
             mutbl: var_mutbl,
@@ -826,35 +1716,19 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             note: NoteNone
         };
 
-        // If this is a `FnMut` or `Fn` closure, then the above is
-        // conceptually a `&mut` or `&` reference, so we have to add a
-        // deref.
-        let cmt_result = match kind {
-            ty::ClosureKind::FnOnce => {
-                cmt_result
-            }
-            ty::ClosureKind::FnMut => {
-                self.env_deref(hir_id, span, upvar_id, var_mutbl, ty::MutBorrow, cmt_result)
-            }
-            ty::ClosureKind::Fn => {
-                self.env_deref(hir_id, span, upvar_id, var_mutbl, ty::ImmBorrow, cmt_result)
-            }
-        };
-
         // If this is a by-ref capture, then the upvar we loaded is
         // actually a reference, so we have to add an implicit deref
         // for that.
-        let upvar_capture = self.tables.upvar_capture(upvar_id);
-        let cmt_result = match upvar_capture {
+        let ret = match capture {
             ty::UpvarCapture::ByValue => {
-                cmt_result
+                upvar
             }
             ty::UpvarCapture::ByRef(upvar_borrow) => {
                 let ptr = BorrowedPtr(upvar_borrow.kind, upvar_borrow.region);
                 cmt_ {
-                    hir_id,
+                    hir_id: var_hir_id,
                     span,
-                    cat: Categorization::Deref(Rc::new(cmt_result), ptr),
+                    cat: Categorization::Deref(Rc::new(upvar), ptr),
                     mutbl: MutabilityCategory::from_borrow_kind(upvar_borrow.kind),
                     ty: var_ty,
                     note: NoteUpvarRef(upvar_id)
@@ -862,9 +1736,8 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             }
         };
 
-        let ret = cmt_result;
-        debug!("cat_upvar ret={:?}", ret);
-        Ok(ret)
+        debug!("cat_captured_var ret={:?}", ret);
+        Ok(Rc::new(ret))
     }
 
     fn env_deref(&self,
@@ -927,12 +1800,12 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
 
     /// Returns the lifetime of a temporary created by expr with id `id`.
     /// This could be `'static` if `id` is part of a constant expression.
-    pub fn temporary_scope(&self, id: hir::ItemLocalId) -> ty::Region<'tcx> {
+    pub fn temporary_scope(&self, id: hir::ItemLocalId) -> TemporaryScope<'tcx> {
         let scope = self.region_scope_tree.temporary_scope(id);
-        self.tcx.mk_region(match scope {
+        TemporaryScope(self.tcx.mk_region(match scope {
             Some(scope) => ty::ReScope(scope),
             None => ty::ReStatic
-        })
+        }))
     }
 
     pub fn cat_rvalue_node(&self,
@@ -946,11 +1819,23 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             span,
             expr_ty,
         );
+        let (scope, promotable) = self.rvalue_scope(hir_id, expr_ty);
+        let mut ret = self.cat_rvalue(hir_id, span, scope, expr_ty);
+        if promotable {
+            ret.note = NoteConstPromotion;
+        }
+        debug!("cat_rvalue_node ret {:?}", ret);
+        ret
+    }
+
+    /// Returns the scope of the temporary holding the value of the
+    /// expression `hir_id`, and whether it is promoted to a constant.
+    fn rvalue_scope(&self, hir_id: hir::HirId, expr_ty: Ty<'tcx>) -> (ty::Region<'tcx>, bool) {
         let promotable = self.rvalue_promotable_map.as_ref().map(|m| m.contains(&hir_id.local_id))
                                                             .unwrap_or(false);
 
         debug!(
-            "cat_rvalue_node: promotable = {:?}",
+            "rvalue_scope: promotable = {:?}",
             promotable,
         );
 
@@ -961,21 +1846,19 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         };
 
         debug!(
-            "cat_rvalue_node: promotable = {:?} (2)",
+            "rvalue_scope: promotable = {:?} (2)",
             promotable,
         );
 
         // Compute maximum lifetime of this rvalue. This is 'static if
         // we can promote to a constant, otherwise equal to enclosing temp
         // lifetime.
-        let re = if promotable {
-            self.tcx.types.re_static
+        let scope = if promotable {
+            TemporaryScope(self.tcx.types.re_static)
         } else {
             self.temporary_scope(hir_id.local_id)
         };
-        let ret = self.cat_rvalue(hir_id, span, re, expr_ty);
-        debug!("cat_rvalue_node ret {:?}", ret);
-        ret
+        (scope.into(), promotable)
     }
 
     pub fn cat_rvalue(&self,
@@ -995,32 +1878,39 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         ret
     }
 
+    /// Creates a cmt for the field `f_index` named `f_ident` of
+    /// `base_cmt`. Fails with `McError::NoSuchField` if the variant of the
+    /// base type being accessed has no such field.
     pub fn cat_field<N: HirNode>(&self,
                                  node: &N,
                                  base_cmt: cmt<'tcx>,
                                  f_index: usize,
                                  f_ident: ast::Ident,
                                  f_ty: Ty<'tcx>)
-                                 -> cmt_<'tcx> {
+                                 -> McResult<'tcx, cmt_<'tcx>> {
+        if !has_field(&base_cmt, f_index, f_ident.name) {
+            debug!("cat_field: {:?} has no field {} `{}`", base_cmt, f_index, f_ident);
+            return Err(McError::NoSuchField(base_cmt.ty, f_index));
+        }
+        let interior = InteriorKind::of_field(base_cmt.ty, FieldIndex(f_index, f_ident.name));
         let ret = cmt_ {
             hir_id: node.hir_id(),
             span: node.span(),
             mutbl: base_cmt.mutbl.inherit(),
-            cat: Categorization::Interior(base_cmt,
-                                          InteriorField(FieldIndex(f_index, f_ident.name))),
+            cat: Categorization::Interior(base_cmt, interior),
             ty: f_ty,
             note: NoteNone
         };
         debug!("cat_field ret {:?}", ret);
-        ret
+        Ok(ret)
     }
 
     fn cat_overloaded_place(
         &self,
         expr: &hir::Expr,
         base: &hir::Expr,
-        note: Note,
-    ) -> McResult<cmt_<'tcx>> {
+        note: Note<'tcx>,
+    ) -> McResult<'tcx, cmt_<'tcx>> {
         debug!(
             "cat_overloaded_place(expr={:?}, base={:?}, note={:?})",
             expr,
@@ -1029,23 +1919,46 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         );
 
         // Reconstruct the output assuming it's a reference with the
-        // same region and mutability as the receiver. This holds for
+        // same region as the receiver. This holds for
         // `Deref(Mut)::Deref(_mut)` and `Index(Mut)::index(_mut)`.
         let place_ty = self.expr_ty(expr)?;
         let base_ty = self.expr_ty_adjusted(base)?;
 
-        let (region, mutbl) = match base_ty.sty {
+        let (region, receiver_mutbl) = match base_ty.sty {
             ty::Ref(region, _, mutbl) => (region, mutbl),
             _ => {
                 span_bug!(expr.span, "cat_overloaded_place: base is not a reference")
             }
         };
+
+        // The mutability is that of the method that was actually
+        // selected (e.g. `Index` vs `IndexMut`), which is the one
+        // of the reference it returns.
+        let method_def_id = match self.tables.type_dependent_defs().get(expr.hir_id) {
+            Some(&Def::Method(method_def_id)) => Some(method_def_id),
+            _ => None,
+        };
+        let mutbl = match method_def_id {
+            Some(method_def_id) => {
+                match self.tcx.fn_sig(method_def_id).skip_binder().output().sty {
+                    ty::Ref(_, _, mutbl) => mutbl,
+                    _ => receiver_mutbl,
+                }
+            }
+            None => receiver_mutbl,
+        };
         let ref_ty = self.tcx.mk_ref(region, ty::TypeAndMut {
             ty: place_ty,
             mutbl,
         });
 
         let base_cmt = Rc::new(self.cat_rvalue_node(expr.hir_id, expr.span, ref_ty));
+        let note = match (note, method_def_id) {
+            (NoteNone, Some(method_def_id)) => {
+                NoteDeref(method_def_id, self.tables.node_substs(expr.hir_id))
+            }
+            _ => note,
+        };
         self.cat_deref(expr, base_cmt, note)
     }
 
@@ -1053,17 +1966,20 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         &self,
         node: &impl HirNode,
         base_cmt: cmt<'tcx>,
-        note: Note,
-    ) -> McResult<cmt_<'tcx>> {
+        note: Note<'tcx>,
+    ) -> McResult<'tcx, cmt_<'tcx>> {
         debug!("cat_deref: base_cmt={:?}", base_cmt);
 
+        // An opaque `impl Deref` type never reaches here: typeck resolves
+        // its derefs through the `Deref` bound, so they are categorized by
+        // `cat_overloaded_place` without revealing the hidden type.
         let base_cmt_ty = base_cmt.ty;
         let deref_ty = match base_cmt_ty.builtin_deref(true) {
             Some(mt) => mt.ty,
             None => {
                 debug!("Explicit deref of non-derefable type: {:?}",
                        base_cmt_ty);
-                return Err(());
+                return Err(McError::NonDerefable(base_cmt_ty));
             }
         };
 
@@ -1089,12 +2005,25 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         Ok(ret)
     }
 
+    /// Returns `ConstIndex` if `index` is an integer literal that fits in
+    /// a `u64`, and `RuntimeIndex` otherwise.
+    fn index_offset_kind(&self, index: &hir::Expr) -> InteriorOffsetKind {
+        if let hir::ExprKind::Lit(ref lit) = index.node {
+            if let ast::LitKind::Int(n, _) = lit.node {
+                if n <= u64::max_value() as u128 {
+                    return InteriorOffsetKind::ConstIndex(n as u64);
+                }
+            }
+        }
+        InteriorOffsetKind::RuntimeIndex
+    }
+
     fn cat_index<N: HirNode>(&self,
                              elt: &N,
                              base_cmt: cmt<'tcx>,
                              element_ty: Ty<'tcx>,
                              context: InteriorOffsetKind)
-                             -> McResult<cmt_<'tcx>> {
+                             -> McResult<'tcx, cmt_<'tcx>> {
         //! Creates a cmt for an indexing operation (`[]`).
         //!
         //! One subtle aspect of indexing that may not be
@@ -1110,14 +2039,38 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         //!
         //! # Parameters
         //! - `elt`: the HIR node being indexed
-        //! - `base_cmt`: the cmt of `elt`
+        //! - `base_cmt`: the cmt of `elt`, which must be an array or a
+        //!   slice; anything else fails with `McError::NonIndexable`
 
+        if base_cmt.ty.builtin_index().is_none() {
+            debug!("cat_index: non-indexable type {:?}", base_cmt);
+            return Err(McError::NonIndexable(base_cmt.ty));
+        }
         let interior_elem = InteriorElement(context);
         let ret = self.cat_imm_interior(elt, base_cmt, element_ty, interior_elem);
         debug!("cat_index ret {:?}", ret);
         return Ok(ret);
     }
 
+    /// Categorizes an element of the array, slice or string `slice_cmt`,
+    /// first dereferencing `slice_cmt` if it is a pointer to one.
+    pub fn cat_slice_elem<N: HirNode>(&self,
+                                      node: &N,
+                                      slice_cmt: cmt<'tcx>,
+                                      elem_ty: Ty<'tcx>,
+                                      context: InteriorOffsetKind)
+                                      -> McResult<'tcx, cmt_<'tcx>> {
+        let slice_cmt = if slice_cmt.ty.builtin_index().is_some() {
+            slice_cmt
+        } else if slice_cmt.ty.builtin_deref(true).is_some() {
+            Rc::new(self.cat_deref(node, slice_cmt, NoteNone)?)
+        } else {
+            debug!("cat_slice_elem: non-indexable type {:?}", slice_cmt);
+            return Err(McError::NonIndexable(slice_cmt.ty));
+        };
+        self.cat_index(node, slice_cmt, elem_ty, context)
+    }
+
     pub fn cat_imm_interior<N:HirNode>(&self,
                                         node: &N,
                                         base_cmt: cmt<'tcx>,
@@ -1145,11 +2098,12 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         let base_did = self.tcx.parent_def_id(variant_did).unwrap();
         if self.tcx.adt_def(base_did).variants.len() != 1 {
             let base_ty = base_cmt.ty;
+            let discr_span = base_cmt.span;
             let ret = Rc::new(cmt_ {
                 hir_id: node.hir_id(),
                 span: node.span(),
                 mutbl: base_cmt.mutbl.inherit(),
-                cat: Categorization::Downcast(base_cmt, variant_did),
+                cat: Categorization::Downcast(base_cmt, variant_did, discr_span),
                 ty: base_ty,
                 note: NoteNone
             });
@@ -1161,14 +2115,255 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         }
     }
 
-    pub fn cat_pattern<F>(&self, cmt: cmt<'tcx>, pat: &hir::Pat, mut op: F) -> McResult<()>
+    /// Categorizes a MIR place, so that MIR-based passes can describe
+    /// places using the same `cmt` representation as the HIR-based ones.
+    ///
+    /// MIR locals carry no HIR identity, so `var_id` is used to map a local
+    /// back to the `NodeId` of the variable it was lowered from; locals
+    /// for which it returns `None` (temporaries, the return place) are
+    /// categorized as rvalues. Every node of the resulting cmt uses the
+    /// id and span of `node`.
+    pub fn cat_place<N, F>(&self,
+                           node: &N,
+                           mir: &mir::Mir<'tcx>,
+                           place: &mir::Place<'tcx>,
+                           var_id: &F)
+                           -> McResult<'tcx, cmt<'tcx>>
+        where N: HirNode, F: Fn(mir::Local) -> Option<ast::NodeId>
+    {
+        debug!("cat_place: place={:?}", place);
+
+        let place_ty = place.ty(mir, self.tcx).to_ty(self.tcx);
+        let ret = match *place {
+            mir::Place::Local(local) => {
+                match var_id(local) {
+                    Some(vid) => cmt_ {
+                        hir_id: node.hir_id(),
+                        span: node.span(),
+                        cat: Categorization::Local(vid),
+                        mutbl: MutabilityCategory::from_local(self.tcx, self.tables, vid),
+                        ty: place_ty,
+                        note: NoteNone
+                    },
+                    None => {
+                        self.cat_rvalue(node.hir_id(), node.span(),
+                                        self.tcx.types.re_erased, place_ty)
+                    }
+                }
+            }
+
+            mir::Place::Promoted(..) => {
+                self.cat_rvalue(node.hir_id(), node.span(), self.tcx.types.re_static, place_ty)
+            }
+
+            mir::Place::Static(ref statik) => {
+                let mutbl = self.tcx.is_static(statik.def_id) == Some(hir::MutMutable);
+                cmt_ {
+                    hir_id: node.hir_id(),
+                    span: node.span(),
+                    cat: Categorization::StaticItem,
+                    mutbl: if mutbl { McDeclared } else { McImmutable },
+                    ty: place_ty,
+                    note: NoteNone
+                }
+            }
+
+            mir::Place::Projection(ref proj) => {
+                let base_cmt = self.cat_place(node, mir, &proj.base, var_id)?;
+                match proj.elem {
+                    mir::ProjectionElem::Deref => {
+                        self.cat_deref(node, base_cmt, NoteNone)?
+                    }
+
+                    mir::ProjectionElem::Field(field, _) => {
+                        let i = field.index();
+                        let name = match proj.base.ty(mir, self.tcx) {
+                            mir::tcx::PlaceTy::Downcast { adt_def, variant_index, .. } => {
+                                adt_def.variants[variant_index].fields[i].ident.name
+                            }
+                            mir::tcx::PlaceTy::Ty { ty } => match ty.sty {
+                                ty::Adt(adt_def, _) => {
+                                    adt_def.non_enum_variant().fields[i].ident.name
+                                }
+                                _ => Name::intern(&i.to_string()),
+                            },
+                        };
+                        let interior = InteriorKind::of_field(base_cmt.ty, FieldIndex(i, name));
+                        self.cat_imm_interior(node, base_cmt, place_ty, interior)
+                    }
+
+                    mir::ProjectionElem::Index(_) => {
+                        self.cat_index(node, base_cmt, place_ty,
+                                       InteriorOffsetKind::RuntimeIndex)?
+                    }
+
+                    mir::ProjectionElem::ConstantIndex { offset, from_end: false, .. } => {
+                        self.cat_index(node, base_cmt, place_ty,
+                                       InteriorOffsetKind::ConstIndex(offset as u64))?
+                    }
+
+                    // Counting from the end and subslicing only come out
+                    // of slice patterns.
+                    mir::ProjectionElem::ConstantIndex { from_end: true, .. } |
+                    mir::ProjectionElem::Subslice { .. } => {
+                        self.cat_index(node, base_cmt, place_ty, InteriorOffsetKind::Pattern)?
+                    }
+
+                    mir::ProjectionElem::Downcast(adt_def, variant_index) => {
+                        let variant_did = adt_def.variants[variant_index].did;
+                        return Ok(self.cat_downcast_if_needed(node, base_cmt, variant_did));
+                    }
+                }
+            }
+        };
+        debug!("cat_place ret {:?}", ret);
+        Ok(Rc::new(ret))
+    }
+
+    /// Categorizes the place reached from `root` through the projections
+    /// in `path`, without needing an expression or a MIR body for it.
+    /// Every node of the resulting cmt uses the id and span of `node`.
+    pub fn cat_place_from_mir_like_path<N: HirNode>(&self,
+                                                    node: &N,
+                                                    root: PathRoot,
+                                                    path: &[PathElem<'tcx>])
+                                                    -> McResult<'tcx, cmt<'tcx>> {
+        debug!("cat_place_from_mir_like_path: root={:?} path={:?}", root, path);
+
+        let mut cmt = Rc::new(match root {
+            PathRoot::Local(vid) => {
+                let ty = self.node_ty(self.tcx.hir.node_to_hir_id(vid))?;
+                self.cat_def(node.hir_id(), node.span(), ty, Def::Local(vid))?
+            }
+            PathRoot::Upvar(upvar_id) => {
+                let var_id = self.tcx.hir.hir_to_node_id(upvar_id.var_id);
+                let fn_node_id = self.tcx.hir.as_local_node_id(upvar_id.closure_expr_id.to_def_id())
+                    .expect("captured variable of a non-local closure");
+                self.cat_upvar(node.hir_id(), node.span(), var_id, fn_node_id)?
+            }
+            PathRoot::Static(def_id) => {
+                let ty = self.tcx.type_of(def_id);
+                let mutbl = self.tcx.is_static(def_id) == Some(hir::MutMutable);
+                self.cat_def(node.hir_id(), node.span(), ty, Def::Static(def_id, mutbl))?
+            }
+        });
+
+        for elem in path {
+            cmt = match *elem {
+                PathElem::Field(i, ty) => {
+                    let ident = match cmt.resolve_field(i) {
+                        Some((_, field_def)) => field_def.ident,
+                        None => ast::Ident::from_str(&i.to_string()),
+                    };
+                    Rc::new(self.cat_field(node, cmt, i, ident, ty)?)
+                }
+                PathElem::Deref => Rc::new(self.cat_deref(node, cmt, NoteNone)?),
+                PathElem::Index(ty) => {
+                    Rc::new(self.cat_index(node, cmt, ty, InteriorOffsetKind::RuntimeIndex)?)
+                }
+                PathElem::Downcast(variant_did) => {
+                    self.cat_downcast_if_needed(node, cmt, variant_did)
+                }
+            };
+        }
+        debug!("cat_place_from_mir_like_path ret {:?}", cmt);
+        Ok(cmt)
+    }
+
+    /// Walks `pat`, invoking `op` with each sub-pattern and the `cmt` of
+    /// the part of `cmt` it matches. Each pattern is visited before the
+    /// patterns nested in it, except that with `SubPatternFirst`, a
+    /// binding such as `x @ Some(_)` is visited after its sub-pattern.
+    pub fn cat_pattern<F>(&self,
+                          cmt: cmt<'tcx>,
+                          pat: &hir::Pat,
+                          order: PatternWalkOrder,
+                          mut op: F)
+                          -> McResult<'tcx, ()>
+        where F: FnMut(cmt<'tcx>, &hir::Pat),
+    {
+        self.cat_pattern_(cmt, pat, order, &mut op)
+    }
+
+    /// Categorizes `node`, which must be an expression or a pattern, by
+    /// looking it up in the HIR map. A pattern is categorized as the part
+    /// of the value it matches: the outermost pattern around it is walked
+    /// with `cat_pattern` starting at the `let` initializer or `match`
+    /// scrutinee, or at a temporary for arguments and uninitialized `let`s.
+    pub fn node_to_cmt<N: HirNode>(&self, node: &N) -> McResult<'tcx, cmt_<'tcx>> {
+        let node_id = self.tcx.hir.hir_to_node_id(node.hir_id());
+        match self.tcx.hir.find(node_id) {
+            Some(hir_map::NodeExpr(expr)) => self.cat_expr(expr),
+            Some(hir_map::NodeBinding(pat)) |
+            Some(hir_map::NodePat(pat)) => self.cat_pat_node(pat),
+            other => span_bug!(node.span(), "node_to_cmt: unexpected node {:?}", other),
+        }
+    }
+
+    fn cat_pat_node(&self, pat: &hir::Pat) -> McResult<'tcx, cmt_<'tcx>> {
+        let mut root = pat;
+        let parent = loop {
+            let parent = self.tcx.hir.get_parent_node(root.id);
+            match self.tcx.hir.find(parent) {
+                Some(hir_map::NodeBinding(p)) |
+                Some(hir_map::NodePat(p)) => root = p,
+                parent => break parent,
+            }
+        };
+        let root_cmt = match parent {
+            Some(hir_map::NodeLocal(&hir::Local { init: Some(ref init), .. })) => {
+                self.cat_expr(init)?
+            }
+            Some(hir_map::NodeExpr(expr @ &hir::Expr {
+                node: hir::ExprKind::Match(..), ..
+            })) => self.cat_match_scrutinee(expr)?,
+            _ => self.cat_rvalue_node(root.hir_id, root.span, self.pat_ty_unadjusted(root)?),
+        };
+
+        let mut ret = None;
+        self.cat_pattern(Rc::new(root_cmt), root, BindingFirst, |cmt, p| {
+            if p.id == pat.id {
+                ret = Some(cmt);
+            }
+        })?;
+        match ret {
+            Some(cmt) => Ok((*cmt).clone()),
+            None => span_bug!(pat.span, "cat_pat_node: pattern not reached from its root"),
+        }
+    }
+
+    /// Like `cat_pattern`, for `pat`, one of the patterns of the match arm
+    /// `arm`. If the arm has a guard, the places bound by the bindings of
+    /// the pattern are marked with `NoteMatchGuard` (unless they already
+    /// carry a note), since the guard uses them before the arm is chosen.
+    pub fn cat_pattern_in_guard<F>(&self,
+                                   cmt: cmt<'tcx>,
+                                   arm: &hir::Arm,
+                                   pat: &hir::Pat,
+                                   mut op: F)
+                                   -> McResult<'tcx, ()>
         where F: FnMut(cmt<'tcx>, &hir::Pat),
     {
-        self.cat_pattern_(cmt, pat, &mut op)
+        if arm.guard.is_none() {
+            return self.cat_pattern_(cmt, pat, BindingFirst, &mut op);
+        }
+        self.cat_pattern_(cmt, pat, BindingFirst, &mut |cmt: cmt<'tcx>, p: &hir::Pat| {
+            match p.node {
+                PatKind::Binding(..) if cmt.note == NoteNone && !cmt.is_upvar_by_value() => {
+                    op(Rc::new(cmt_ { note: NoteMatchGuard, ..(*cmt).clone() }), p)
+                }
+                _ => op(cmt, p),
+            }
+        })
     }
 
     // FIXME(#19596) This is a workaround, but there should be a better way to do this
-    fn cat_pattern_<F>(&self, mut cmt: cmt<'tcx>, pat: &hir::Pat, op: &mut F) -> McResult<()>
+    fn cat_pattern_<F>(&self,
+                       mut cmt: cmt<'tcx>,
+                       pat: &hir::Pat,
+                       order: PatternWalkOrder,
+                       op: &mut F)
+                       -> McResult<'tcx, ()>
         where F : FnMut(cmt<'tcx>, &hir::Pat)
     {
         // Here, `cmt` is the categorization for the value being
@@ -1273,7 +2468,16 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
         // `Some(x)` (which matches). Recursing once more, `*&Some(3)` and the pattern `Some(x)`
         // result in the cmt `Downcast<Some>(*&Some(3)).0` associated to `x` and invoke `op` with
         // that (where the `ref` on `x` is implied).
-        op(cmt.clone(), pat);
+        //
+        // With `SubPatternFirst`, a binding with a sub-pattern is only
+        // passed to `op` once its sub-pattern has been walked, below.
+        let binding_last = match (order, &pat.node) {
+            (SubPatternFirst, &PatKind::Binding(.., Some(_))) => true,
+            _ => false,
+        };
+        if !binding_last {
+            op(cmt.clone(), pat);
+        }
 
         match pat.node {
           PatKind::TupleStruct(ref qpath, ref subpats, ddpos) => {
@@ -1281,7 +2485,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             let (cmt, expected_len) = match def {
                 Def::Err => {
                     debug!("access to unresolvable pattern {:?}", pat);
-                    return Err(())
+                    return Err(McError::TaintedByErrors)
                 }
                 Def::VariantCtor(def_id, CtorKind::Fn) => {
                     let enum_def = self.tcx.parent_def_id(def_id).unwrap();
@@ -1308,7 +2512,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                 let subpat_ty = self.pat_ty_adjusted(&subpat)?; // see (*2)
                 let interior = InteriorField(FieldIndex(i, Name::intern(&i.to_string())));
                 let subcmt = Rc::new(self.cat_imm_interior(pat, cmt.clone(), subpat_ty, interior));
-                self.cat_pattern_(subcmt, &subpat, op)?;
+                self.cat_pattern_(subcmt, &subpat, order, op)?;
             }
           }
 
@@ -1318,7 +2522,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             let cmt = match def {
                 Def::Err => {
                     debug!("access to unresolvable pattern {:?}", pat);
-                    return Err(())
+                    return Err(McError::TaintedByErrors)
                 },
                 Def::Variant(variant_did) |
                 Def::VariantCtor(variant_did, ..) => {
@@ -1331,13 +2535,16 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                 let field_ty = self.pat_ty_adjusted(&fp.node.pat)?; // see (*2)
                 let f_index = self.tcx.field_index(fp.node.id, self.tables);
                 let cmt_field = Rc::new(self.cat_field(pat, cmt.clone(), f_index,
-                                                       fp.node.ident, field_ty));
-                self.cat_pattern_(cmt_field, &fp.node.pat, op)?;
+                                                       fp.node.ident, field_ty)?);
+                self.cat_pattern_(cmt_field, &fp.node.pat, order, op)?;
             }
           }
 
           PatKind::Binding(.., Some(ref subpat)) => {
-              self.cat_pattern_(cmt, &subpat, op)?;
+              self.cat_pattern_(cmt.clone(), &subpat, order, op)?;
+              if binding_last {
+                  op(cmt, pat);
+              }
           }
 
           PatKind::Tuple(ref subpats, ddpos) => {
@@ -1350,7 +2557,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                 let subpat_ty = self.pat_ty_adjusted(&subpat)?; // see (*2)
                 let interior = InteriorField(FieldIndex(i, Name::intern(&i.to_string())));
                 let subcmt = Rc::new(self.cat_imm_interior(pat, cmt.clone(), subpat_ty, interior));
-                self.cat_pattern_(subcmt, &subpat, op)?;
+                self.cat_pattern_(subcmt, &subpat, order, op)?;
             }
           }
 
@@ -1359,7 +2566,7 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
             // PatKind::Ref since that information is already contained
             // in the type.
             let subcmt = Rc::new(self.cat_deref(pat, cmt, NoteNone)?);
-            self.cat_pattern_(subcmt, &subpat, op)?;
+            self.cat_pattern_(subcmt, &subpat, order, op)?;
           }
 
           PatKind::Slice(ref before, ref slice, ref after) => {
@@ -1367,19 +2574,19 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
                 Some(ty) => ty,
                 None => {
                     debug!("Explicit index of non-indexable type {:?}", cmt);
-                    return Err(());
+                    return Err(McError::NonIndexable(cmt.ty));
                 }
             };
             let context = InteriorOffsetKind::Pattern;
-            let elt_cmt = Rc::new(self.cat_index(pat, cmt, element_ty, context)?);
+            let elt_cmt = Rc::new(self.cat_slice_elem(pat, cmt, element_ty, context)?);
             for before_pat in before {
-                self.cat_pattern_(elt_cmt.clone(), &before_pat, op)?;
+                self.cat_pattern_(elt_cmt.clone(), &before_pat, order, op)?;
             }
             if let Some(ref slice_pat) = *slice {
-                self.cat_pattern_(elt_cmt.clone(), &slice_pat, op)?;
+                self.cat_pattern_(elt_cmt.clone(), &slice_pat, order, op)?;
             }
             for after_pat in after {
-                self.cat_pattern_(elt_cmt.clone(), &after_pat, op)?;
+                self.cat_pattern_(elt_cmt.clone(), &after_pat, order, op)?;
             }
           }
 
@@ -1397,14 +2604,38 @@ impl<'a, 'gcx, 'tcx> MemCategorizationContext<'a, 'gcx, 'tcx> {
 pub enum Aliasability {
     FreelyAliasable(AliasableReason),
     NonAliasable,
+    /// The contents of a `Box` that is itself freely aliasable, as given
+    /// by the inner aliasability.
     ImmutableUnique(Box<Aliasability>),
 }
 
-#[derive(Copy, Clone, Debug)]
+impl Aliasability {
+    /// Returns true if the place may be freely aliased, looking through
+    /// `ImmutableUnique`.
+    pub fn is_aliasable(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// Returns why the place may be freely aliased, looking through
+    /// `ImmutableUnique`, or `None` if it is not aliasable.
+    pub fn reason(&self) -> Option<AliasableReason> {
+        match *self {
+            FreelyAliasable(reason) => Some(reason),
+            NonAliasable => None,
+            ImmutableUnique(ref inner) => inner.reason(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum AliasableReason {
     AliasableBorrowed,
+    /// Borrowed through a reference passed in as the given function (or
+    /// closure) argument, whose lifetime is chosen by the caller.
+    AliasableArgument(ast::NodeId),
     AliasableStatic,
     AliasableStaticMut,
+    AliasableRawPtr,
 }
 
 impl<'tcx> cmt_<'tcx> {
@@ -1412,47 +2643,333 @@ impl<'tcx> cmt_<'tcx> {
         //! Returns `self` after stripping away any derefs or
         //! interior content. The return value is basically the `cmt` which
         //! determines how long the value in `self` remains live.
+        //! The chain is walked with a loop, as it can be arbitrarily long
+        //! (e.g. a deeply nested `Box`).
+
+        let mut cmt = self;
+        loop {
+            cmt = match cmt.cat {
+                Categorization::Rvalue(..) |
+                Categorization::PtrCast(..) |
+                Categorization::StaticItem |
+                Categorization::Local(..) |
+                Categorization::Deref(_, UnsafePtr(..)) |
+                Categorization::Deref(_, BorrowedPtr(..)) |
+                Categorization::Upvar(..) => {
+                    return cmt.clone();
+                }
+                Categorization::Downcast(ref b, _, _) |
+                Categorization::Interior(ref b, _) |
+                Categorization::Deref(ref b, Unique) => b,
+            };
+        }
+    }
+
+    /// Returns the place `self` is projected from: the local, upvar,
+    /// static or rvalue at the base of its chain of derefs, interiors,
+    /// downcasts and casts. Unlike `guarantor`, this also goes through
+    /// borrowed and unsafe pointers, so it is where the storage of the
+    /// projection starts rather than what keeps the value alive.
+    pub fn root(&self) -> cmt<'tcx> {
+        let mut cmt = self;
+        loop {
+            cmt = match cmt.cat {
+                Categorization::Rvalue(..) |
+                Categorization::StaticItem |
+                Categorization::Local(..) |
+                Categorization::Upvar(..) => {
+                    return Rc::new(cmt.clone());
+                }
+                Categorization::Downcast(ref b, _, _) |
+                Categorization::Interior(ref b, _) |
+                Categorization::PtrCast(ref b, ..) |
+                Categorization::Deref(ref b, _) => b,
+            };
+        }
+    }
+
+    /// Returns `self` with all `Downcast` layers removed, so that the
+    /// place built on top of a downcast is built directly on the enum
+    /// value instead. `cat_downcast_if_needed` elides the downcast for
+    /// univariant enums, so comparing the stripped forms with
+    /// `refers_to_same_place_as` does not depend on whether it was elided.
+    /// Note that fields with the same index in different variants then
+    /// compare equal as well.
+    pub fn strip_downcasts(&self) -> cmt<'tcx> {
+        // The chain is walked and rebuilt with loops, as it can be
+        // arbitrarily long.
+        let mut chain = vec![];
+        let mut cmt = self;
+        loop {
+            chain.push(cmt);
+            cmt = match cmt.cat {
+                Categorization::Rvalue(..) |
+                Categorization::StaticItem |
+                Categorization::Local(..) |
+                Categorization::Upvar(..) => break,
+                Categorization::Downcast(ref b, _, _) |
+                Categorization::Interior(ref b, _) |
+                Categorization::PtrCast(ref b, ..) |
+                Categorization::Deref(ref b, _) => b,
+            };
+        }
+
+        let root = chain.pop().unwrap();
+        let mut stripped = Rc::new(root.clone());
+        for cmt in chain.into_iter().rev() {
+            let cat = match cmt.cat {
+                Categorization::Downcast(..) => continue,
+                Categorization::Interior(_, ik) => Categorization::Interior(stripped, ik),
+                Categorization::PtrCast(_, ty, scope) => {
+                    Categorization::PtrCast(stripped, ty, scope)
+                }
+                Categorization::Deref(_, ptr) => Categorization::Deref(stripped, ptr),
+                Categorization::Rvalue(..) |
+                Categorization::StaticItem |
+                Categorization::Local(..) |
+                Categorization::Upvar(..) => bug!("strip_downcasts: {:?} is not a projection", cmt),
+            };
+            stripped = Rc::new(cmt_ {
+                hir_id: cmt.hir_id,
+                span: cmt.span,
+                cat,
+                mutbl: cmt.mutbl,
+                ty: cmt.ty,
+                note: cmt.note,
+            });
+        }
+        stripped
+    }
 
+    /// Returns true if reaching `self` from its `guarantor` goes through
+    /// the deref of a `Box`. Moving out of such places is allowed, while
+    /// moving out of other derefs is not.
+    pub fn crosses_box_deref(&self) -> bool {
         match self.cat {
+            Categorization::Deref(_, Unique) => true,
+            Categorization::Downcast(ref b, _, _) |
+            Categorization::Interior(ref b, _) => {
+                b.crosses_box_deref()
+            }
             Categorization::Rvalue(..) |
+            Categorization::PtrCast(..) |
             Categorization::StaticItem |
             Categorization::Local(..) |
+            Categorization::Upvar(..) |
             Categorization::Deref(_, UnsafePtr(..)) |
-            Categorization::Deref(_, BorrowedPtr(..)) |
-            Categorization::Upvar(..) => {
-                (*self).clone()
+            Categorization::Deref(_, BorrowedPtr(..)) => {
+                false
             }
-            Categorization::Downcast(ref b, _) |
+        }
+    }
+
+    /// Returns true if the shape of this place allows moving out of it:
+    /// its `guarantor` is a local, an upvar captured by value or a temporary,
+    /// so that reaching it only goes through interiors, downcasts and
+    /// `Box` derefs. This is what allows moving `box_s.f` but not
+    /// `ref_s.f`. Moves out of statics, of indexed elements and out of
+    /// types implementing `Drop` are still rejected by borrowck.
+    pub fn can_move_out(&self) -> bool {
+        let mut cmt = self;
+        loop {
+            cmt = match cmt.cat {
+                Categorization::Rvalue(..) |
+                Categorization::PtrCast(..) |
+                Categorization::Local(..) |
+                Categorization::Upvar(..) => {
+                    return true;
+                }
+                Categorization::StaticItem |
+                Categorization::Deref(_, UnsafePtr(..)) |
+                Categorization::Deref(_, BorrowedPtr(..)) => {
+                    return false;
+                }
+                Categorization::Downcast(ref b, _, _) |
+                Categorization::Interior(ref b, _) |
+                Categorization::Deref(ref b, Unique) => b,
+            };
+        }
+    }
+
+    /// Returns the id of the local variable this place is rooted at, if
+    /// it can be reached from that local without going through a
+    /// borrowed or unsafe pointer (i.e. if the local is the `guarantor`).
+    pub fn innermost_local(&self) -> Option<ast::NodeId> {
+        match self.cat {
+            Categorization::Local(vid) => Some(vid),
+            Categorization::Downcast(ref b, _, _) |
             Categorization::Interior(ref b, _) |
             Categorization::Deref(ref b, Unique) => {
-                b.guarantor()
+                b.innermost_local()
+            }
+            Categorization::Rvalue(..) |
+            Categorization::PtrCast(..) |
+            Categorization::StaticItem |
+            Categorization::Upvar(..) |
+            Categorization::Deref(_, UnsafePtr(..)) |
+            Categorization::Deref(_, BorrowedPtr(..)) => {
+                None
+            }
+        }
+    }
+
+    /// Returns the longest place that both `self` and `other` are reached
+    /// through, or `None` if they are rooted at different places. E.g.
+    /// the common prefix of `x.a` and `x.b` is `x`, and that of `x.a`
+    /// and `x.a.b` is `x.a`. Indexing steps are considered equal
+    /// whatever the index, so the result is conservative.
+    pub fn common_prefix(&self, other: &cmt_<'tcx>) -> Option<cmt<'tcx>> {
+        let (ours, theirs) = (self.prefixes(), other.prefixes());
+        let mut prefix = None;
+        for (a, b) in ours.iter().zip(theirs.iter()) {
+            if !a.is_same_step_as(b) {
+                break;
+            }
+            prefix = Some(a.clone());
+        }
+        prefix
+    }
+
+    /// The places `self` is reached through, starting at its root and
+    /// ending with `self`.
+    fn prefixes(&self) -> Vec<cmt<'tcx>> {
+        let mut prefixes = vec![Rc::new(self.clone())];
+        loop {
+            let base = match prefixes.last().unwrap().cat {
+                Categorization::Deref(ref b, _) |
+                Categorization::Interior(ref b, _) |
+                Categorization::Downcast(ref b, _, _) |
+                Categorization::PtrCast(ref b, ..) => b.clone(),
+                Categorization::Rvalue(..) |
+                Categorization::StaticItem |
+                Categorization::Upvar(..) |
+                Categorization::Local(..) => break,
+            };
+            prefixes.push(base);
+        }
+        prefixes.reverse();
+        prefixes
+    }
+
+    /// Whether `self` and `other` take the same step from their bases,
+    /// which are assumed to be the same place.
+    fn is_same_step_as(&self, other: &cmt_<'tcx>) -> bool {
+        match (&self.cat, &other.cat) {
+            (&Categorization::Local(a), &Categorization::Local(b)) => a == b,
+            (&Categorization::Upvar(ref a), &Categorization::Upvar(ref b)) => a.id == b.id,
+            (&Categorization::Rvalue(..), &Categorization::Rvalue(..)) |
+            (&Categorization::StaticItem, &Categorization::StaticItem) => {
+                self.hir_id == other.hir_id
+            }
+            (&Categorization::Deref(..), &Categorization::Deref(..)) => true,
+            (&Categorization::Interior(_, a), &Categorization::Interior(_, b)) => {
+                match (a, b) {
+                    (InteriorElement(_), InteriorElement(_)) => true,
+                    _ => a == b,
+                }
+            }
+            (&Categorization::Downcast(_, a, _), &Categorization::Downcast(_, b, _)) => a == b,
+            (&Categorization::PtrCast(_, a, _), &Categorization::PtrCast(_, b, _)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Returns the span of the discriminant inspected by the innermost
+    /// enum downcast this place goes through, if any.
+    pub fn downcast_discr_span(&self) -> Option<Span> {
+        match self.cat {
+            Categorization::Downcast(_, _, span) => Some(span),
+            Categorization::Interior(ref b, _) |
+            Categorization::PtrCast(ref b, ..) |
+            Categorization::Deref(ref b, _) => {
+                b.downcast_discr_span()
+            }
+            Categorization::Rvalue(..) |
+            Categorization::StaticItem |
+            Categorization::Upvar(..) |
+            Categorization::Local(..) => {
+                None
+            }
+        }
+    }
+
+    /// Returns the impl of `Deref` whose shared (non-`DerefMut`) deref this
+    /// place is reached through, if that is what makes it immutable.
+    /// Overloaded indexing is not included, as its errors already mention
+    /// `IndexMut`.
+    pub fn immutable_overloaded_deref_impl(&self, tcx: TyCtxt<'_, '_, 'tcx>) -> Option<DefId> {
+        match self.cat {
+            Categorization::Deref(_, BorrowedPtr(ty::ImmBorrow, _)) => {
+                self.note.overloaded_deref_impl(tcx)
+            }
+            Categorization::Interior(ref b, _) |
+            Categorization::Downcast(ref b, ..) => {
+                b.immutable_overloaded_deref_impl(tcx)
+            }
+            Categorization::Rvalue(..) |
+            Categorization::StaticItem |
+            Categorization::Upvar(..) |
+            Categorization::Local(..) |
+            Categorization::PtrCast(..) |
+            Categorization::Deref(..) => {
+                None
             }
         }
     }
 
-    /// Returns `FreelyAliasable(_)` if this place represents a freely aliasable pointer type.
-    pub fn freely_aliasable(&self) -> Aliasability {
+    /// Returns `FreelyAliasable(_)` if this place represents a freely aliasable pointer type,
+    /// wrapped in `ImmutableUnique` if it is reached through the deref of a `Box`.
+    ///
+    /// The contents of a raw pointer are reported as `NonAliasable`: they may well be
+    /// aliased, but dereferencing the pointer is unsafe, so borrowck leaves them alone.
+    pub fn freely_aliasable(&self, tcx: TyCtxt) -> Aliasability {
+        self.aliasability(tcx, false)
+    }
+
+    /// Like `freely_aliasable`, but reports the contents of a raw pointer as
+    /// `FreelyAliasable(AliasableRawPtr)`.
+    pub fn freely_aliasable_conservative(&self, tcx: TyCtxt) -> Aliasability {
+        self.aliasability(tcx, true)
+    }
+
+    fn aliasability(&self, tcx: TyCtxt, raw_ptrs_alias: bool) -> Aliasability {
         // Maybe non-obvious: copied upvars can only be considered
         // non-aliasable in once closures, since any other kind can be
         // aliased and eventually recused.
 
         match self.cat {
+            Categorization::Deref(ref b, Unique) => {
+                // The contents of a box are as aliasable as the box itself,
+                // but remember that they are reached through the box.
+                match b.aliasability(tcx, raw_ptrs_alias) {
+                    sub @ FreelyAliasable(_) => ImmutableUnique(Box::new(sub)),
+                    sub => sub,
+                }
+            }
+
             Categorization::Deref(ref b, BorrowedPtr(ty::MutBorrow, _)) |
             Categorization::Deref(ref b, BorrowedPtr(ty::UniqueImmBorrow, _)) |
-            Categorization::Deref(ref b, Unique) |
-            Categorization::Downcast(ref b, _) |
-            Categorization::Interior(ref b, _) => {
+            Categorization::Downcast(ref b, _, _) |
+            Categorization::Interior(ref b, _) |
+            Categorization::PtrCast(ref b, ..) => {
                 // Aliasability depends on base cmt
-                b.freely_aliasable()
+                b.aliasability(tcx, raw_ptrs_alias)
             }
 
             Categorization::Rvalue(..) |
             Categorization::Local(..) |
-            Categorization::Upvar(..) |
-            Categorization::Deref(_, UnsafePtr(..)) => { // yes, it's aliasable, but...
+            Categorization::Upvar(..) => {
                 NonAliasable
             }
 
+            Categorization::Deref(_, UnsafePtr(..)) => {
+                if raw_ptrs_alias {
+                    FreelyAliasable(AliasableRawPtr)
+                } else {
+                    NonAliasable
+                }
+            }
+
             Categorization::StaticItem => {
                 if self.mutbl.is_mutable() {
                     FreelyAliasable(AliasableStaticMut)
@@ -1461,30 +2978,203 @@ impl<'tcx> cmt_<'tcx> {
                 }
             }
 
-            Categorization::Deref(_, BorrowedPtr(ty::ImmBorrow, _)) => {
-                FreelyAliasable(AliasableBorrowed)
+            Categorization::Deref(ref b, BorrowedPtr(ty::ImmBorrow, _)) => {
+                // The reference was passed in as an argument only if it is
+                // reached from that argument without going through another
+                // reference, e.g. not for `**x` where `x: &&T`.
+                match b.innermost_local() {
+                    Some(vid) if tcx.hir.is_argument(vid) => {
+                        FreelyAliasable(AliasableArgument(vid))
+                    }
+                    _ => FreelyAliasable(AliasableBorrowed),
+                }
             }
         }
     }
 
+    /// Returns the region of the temporary scope of this place if it is an
+    /// rvalue or the result of a pointer cast: `'static` for promoted
+    /// rvalues, and the scope at the end of which the temporary is dropped
+    /// otherwise.
+    pub fn rvalue_temp_scope(&self) -> Option<ty::Region<'tcx>> {
+        match self.cat {
+            Categorization::Rvalue(region) |
+            Categorization::PtrCast(_, _, region) => Some(region),
+            _ => None,
+        }
+    }
+
+    /// Returns the base and pointer kind of this place if it is a deref.
+    pub fn try_deref(&self) -> Option<(&cmt<'tcx>, PointerKind<'tcx>)> {
+        match self.cat {
+            Categorization::Deref(ref inner, ptr) => Some((inner, ptr)),
+            _ => None,
+        }
+    }
+
     // Digs down through one or two layers of deref and grabs the
     // Categorization of the cmt for the upvar if a note indicates there is
     // one.
     pub fn upvar_cat(&self) -> Option<&Categorization<'tcx>> {
         match self.note {
             NoteClosureEnv(..) | NoteUpvarRef(..) => {
-                Some(match self.cat {
-                    Categorization::Deref(ref inner, _) => {
-                        match inner.cat {
-                            Categorization::Deref(ref inner, _) => &inner.cat,
-                            Categorization::Upvar(..) => &inner.cat,
-                            _ => bug!()
-                        }
-                    }
-                    _ => bug!()
+                let (inner, _) = self.try_deref().unwrap_or_else(|| bug!());
+                Some(match inner.try_deref() {
+                    Some((inner, _)) => &inner.cat,
+                    None => match inner.cat {
+                        Categorization::Upvar(..) => &inner.cat,
+                        _ => bug!()
+                    },
                 })
             }
-            NoteIndex | NoteNone => None
+            NoteIndex | NoteYield | NoteTry | NoteDeref(..) |
+            NoteConstPromotion | NoteMatchGuard | NoteNone => {
+                None
+            }
+        }
+    }
+
+    /// Returns true if `self` is a use of a variable captured by reference,
+    /// i.e. the deref of the captured reference.
+    pub fn is_upvar_by_ref(&self) -> bool {
+        match self.note {
+            NoteUpvarRef(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if `self` is a use of a variable captured by value:
+    /// the upvar itself in a `FnOnce` closure or a generator, and the deref
+    /// of the environment pointer in a `Fn` or `FnMut` closure. Only
+    /// meaningful for the outermost `cmt` of a use, as the same shapes
+    /// also appear beneath the deref of a by-reference capture.
+    pub fn is_upvar_by_value(&self) -> bool {
+        match (&self.cat, self.note) {
+            (&Categorization::Upvar(..), NoteNone) => true,
+            (&Categorization::Deref(ref base, _), NoteClosureEnv(..)) => {
+                match base.cat {
+                    Categorization::Upvar(..) => true,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    // Walks down the chain of derefs and reports whether it bottoms out in
+    // an upvar, without building any intermediate `Option`.
+    pub fn contains_upvar(&self) -> bool {
+        match self.cat {
+            Categorization::Upvar(..) => true,
+            Categorization::Deref(ref b, _) => b.contains_upvar(),
+            _ => false,
+        }
+    }
+
+    /// Returns the number of `Deref`, `Interior` and `Downcast` steps
+    /// between `self` and the root of the place, so `x` has depth 0, `x.f`
+    /// depth 1 and `(*x).f[i]` depth 3. Casts are not counted. A `Downcast`
+    /// counts as a step whenever it is present, but `cat_downcast_if_needed`
+    /// elides it for univariant enums, so a field of such an enum is one step
+    /// shallower than it would be in an enum with several variants. Meant as
+    /// a cheap key for preferring the most specific of several places in
+    /// diagnostics.
+    pub fn projection_depth(&self) -> usize {
+        match self.cat {
+            Categorization::Rvalue(..) |
+            Categorization::StaticItem |
+            Categorization::Local(..) |
+            Categorization::Upvar(..) => 0,
+            Categorization::Deref(ref b, _) |
+            Categorization::Interior(ref b, _) |
+            Categorization::Downcast(ref b, _, _) => b.projection_depth() + 1,
+            Categorization::PtrCast(ref b, ..) => b.projection_depth(),
+        }
+    }
+
+    /// Returns true if `self` and `other` denote the same place. Unlike
+    /// `==`, this only looks at the categorization and type, so two
+    /// occurrences of the same path in the source compare equal. Places
+    /// that cannot be told apart from the categorization alone (rvalues,
+    /// statics and indexed elements) never compare equal.
+    pub fn refers_to_same_place_as(&self, other: &cmt_<'tcx>) -> bool {
+        if self.ty != other.ty {
+            return false;
+        }
+        match (&self.cat, &other.cat) {
+            (&Categorization::Local(a), &Categorization::Local(b)) => a == b,
+            (&Categorization::Upvar(a), &Categorization::Upvar(b)) => a == b,
+            (&Categorization::Deref(ref a, pa), &Categorization::Deref(ref b, pb)) => {
+                // Overloaded derefs borrow at a fresh region each time, so
+                // the regions are not compared.
+                let same_ptr = match (pa, pb) {
+                    (Unique, Unique) => true,
+                    (BorrowedPtr(ka, _), BorrowedPtr(kb, _)) => ka == kb,
+                    (UnsafePtr(ma), UnsafePtr(mb)) => ma == mb,
+                    _ => false,
+                };
+                same_ptr && a.refers_to_same_place_as(b)
+            }
+            (&Categorization::Interior(ref a, ia @ InteriorField(_)),
+             &Categorization::Interior(ref b, ib @ InteriorField(_))) |
+            (&Categorization::Interior(ref a, ia @ InteriorUnionField(_)),
+             &Categorization::Interior(ref b, ib @ InteriorUnionField(_))) => {
+                ia == ib && a.refers_to_same_place_as(b)
+            }
+            (&Categorization::Downcast(ref a, da, _), &Categorization::Downcast(ref b, db, _)) => {
+                da == db && a.refers_to_same_place_as(b)
+            }
+            (&Categorization::PtrCast(ref a, ..), &Categorization::PtrCast(ref b, ..)) => {
+                a.refers_to_same_place_as(b)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the `DefId` of the closure this place is captured by, if
+    /// it is reached through an upvar.
+    pub fn upvar_def_id(&self) -> Option<DefId> {
+        match self.upvar_cat() {
+            Some(&Categorization::Upvar(ref upvar)) => return Some(upvar.closure_def_id()),
+            _ => {}
+        }
+        match self.cat {
+            Categorization::Upvar(ref upvar) => Some(upvar.closure_def_id()),
+            Categorization::Deref(ref b, _) |
+            Categorization::Interior(ref b, _) |
+            Categorization::Downcast(ref b, _, _) |
+            Categorization::PtrCast(ref b, ..) => b.upvar_def_id(),
+            Categorization::Rvalue(..) |
+            Categorization::StaticItem |
+            Categorization::Local(..) => None,
+        }
+    }
+
+    /// Builds a `DebugNode` tree for this place, with the base of each
+    /// deref, interior, downcast or cast as its child.
+    pub fn to_debug_tree(&self, tcx: TyCtxt<'_, '_, 'tcx>) -> DebugNode {
+        let (kind, base) = match self.cat {
+            Categorization::Rvalue(r) => (format!("rvalue({:?})", r), None),
+            Categorization::StaticItem => ("static".to_string(), None),
+            Categorization::Upvar(ref upvar) => (format!("upvar({:?})", upvar), None),
+            Categorization::Local(id) => (format!("local({})", id), None),
+            Categorization::Deref(ref b, pk) => (format!("deref({})", ptr_sigil(pk)), Some(b)),
+            Categorization::Interior(ref b, ik) => (format!("interior({:?})", ik), Some(b)),
+            Categorization::Downcast(ref b, did, _) => {
+                (format!("downcast({})", tcx.item_path_str(did)), Some(b))
+            }
+            Categorization::PtrCast(ref b, ..) => ("ptr-cast".to_string(), Some(b)),
+        };
+        let kind = match self.note {
+            NoteNone => kind,
+            note => format!("{} [{}]", kind, note.note_with_tcx(tcx)),
+        };
+        DebugNode {
+            kind,
+            mutbl: format!("{:?}", self.mutbl),
+            ty: self.ty.to_string(),
+            span: tcx.sess.source_map().span_to_string(self.span),
+            children: base.map(|b| b.to_debug_tree(tcx)).into_iter().collect(),
         }
     }
 
@@ -1493,7 +3183,8 @@ impl<'tcx> cmt_<'tcx> {
             Categorization::StaticItem => {
                 "static item".to_string()
             }
-            Categorization::Rvalue(..) => {
+            Categorization::Rvalue(..) |
+            Categorization::PtrCast(..) => {
                 "non-place".to_string()
             }
             Categorization::Local(vid) => {
@@ -1527,10 +3218,16 @@ impl<'tcx> cmt_<'tcx> {
                     }
                 }
             }
-            Categorization::Interior(_, InteriorField(..)) => {
-                "field".to_string()
+            Categorization::Interior(ref base, InteriorField(field_index)) |
+            Categorization::Interior(ref base, InteriorUnionField(field_index)) => {
+                let field_def = match base.ty.sty {
+                    ty::Adt(..) => base.resolve_field(field_index.0).map(|(_, f)| f),
+                    _ => None,
+                };
+                format!("field `{}`", field_index.display_name(field_def))
             }
-            Categorization::Interior(_, InteriorElement(InteriorOffsetKind::Index)) => {
+            Categorization::Interior(_, InteriorElement(InteriorOffsetKind::ConstIndex(_))) |
+            Categorization::Interior(_, InteriorElement(InteriorOffsetKind::RuntimeIndex)) => {
                 "indexed content".to_string()
             }
             Categorization::Interior(_, InteriorElement(InteriorOffsetKind::Pattern)) => {
@@ -1539,11 +3236,22 @@ impl<'tcx> cmt_<'tcx> {
             Categorization::Upvar(ref var) => {
                 var.to_string()
             }
-            Categorization::Downcast(ref cmt, _) => {
+            Categorization::Downcast(ref cmt, _, _) => {
                 cmt.descriptive_string(tcx)
             }
         }
     }
+
+    /// Returns `descriptive_string` along with the indefinite article that
+    /// goes before it in a message, e.g. `("an", "indexed content")`.
+    pub fn descriptive_string_article(&self, tcx: TyCtxt) -> (&'static str, String) {
+        let descr = self.descriptive_string(tcx);
+        let article = match descr.chars().find(|c| c.is_alphabetic()) {
+            Some('a') | Some('e') | Some('i') | Some('o') | Some('u') => "an",
+            _ => "a",
+        };
+        (article, descr)
+    }
 }
 
 pub fn ptr_sigil(ptr: PointerKind) -> &'static str {
@@ -1556,10 +3264,40 @@ pub fn ptr_sigil(ptr: PointerKind) -> &'static str {
     }
 }
 
+/// Like `ptr_sigil`, but for use in user-facing messages: a unique
+/// immutable borrow only ever arises from a closure capturing a `&mut`,
+/// so it is rendered the way the user wrote it.
+pub fn ptr_sigil_user(ptr: PointerKind) -> &'static str {
+    match ptr {
+        BorrowedPtr(ty::UniqueImmBorrow, _) => "&mut",
+        _ => ptr_sigil(ptr),
+    }
+}
+
+/// Returns true if the variant of the type of `base` that `base` is
+/// known to be (through a downcast, or by having a single variant) has a
+/// field `index` named `name`. Types other than ADTs and tuples are not
+/// checked.
+fn has_field(base: &cmt_, index: usize, name: Name) -> bool {
+    match base.ty.sty {
+        ty::Adt(adt_def, _) => {
+            let variant_def = match base.cat {
+                Categorization::Downcast(_, variant_did, _) => adt_def.variant_with_id(variant_did),
+                _ if adt_def.variants.len() == 1 => &adt_def.variants[0],
+                _ => return false,
+            };
+            variant_def.fields.get(index).map_or(false, |field_def| field_def.ident.name == name)
+        }
+        ty::Tuple(tys) => index < tys.len(),
+        _ => true,
+    }
+}
+
 impl fmt::Debug for InteriorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             InteriorField(FieldIndex(_, info)) => write!(f, "{}", info),
+            InteriorUnionField(FieldIndex(_, info)) => write!(f, "union {}", info),
             InteriorElement(..) => write!(f, "[]"),
         }
     }
@@ -1581,3 +3319,93 @@ impl fmt::Display for Upvar {
         write!(f, "captured outer variable in an `{}` closure", kind)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Aliasability, AliasableReason, MutabilityCategory};
+    use super::{Categorization, FieldIndex, InteriorKind, InteriorOffsetKind, Note, PointerKind};
+    use hir;
+    use serialize::{Decodable, Encodable};
+    use serialize::opaque;
+    use std::fmt::Debug;
+    use syntax::ast;
+    use syntax::symbol::Symbol;
+
+    fn round_trip<T: Encodable + Decodable + PartialEq + Debug>(value: T) {
+        let mut encoder = opaque::Encoder::new(Vec::new());
+        value.encode(&mut encoder).unwrap();
+        let data = encoder.into_inner();
+        let mut decoder = opaque::Decoder::new(&data, 0);
+        assert_eq!(T::decode(&mut decoder).unwrap(), value);
+    }
+
+    #[test]
+    fn aliasability_reason() {
+        let aliasable = Aliasability::FreelyAliasable(AliasableReason::AliasableBorrowed);
+        assert!(aliasable.is_aliasable());
+        assert_eq!(aliasable.reason(), Some(AliasableReason::AliasableBorrowed));
+
+        assert!(!Aliasability::NonAliasable.is_aliasable());
+        assert_eq!(Aliasability::NonAliasable.reason(), None);
+    }
+
+    #[test]
+    fn aliasability_reason_through_immutable_unique() {
+        let nested = Aliasability::ImmutableUnique(
+            Box::new(Aliasability::FreelyAliasable(AliasableReason::AliasableStatic)));
+        assert!(nested.is_aliasable());
+        assert_eq!(nested.reason(), Some(AliasableReason::AliasableStatic));
+
+        let unique = Aliasability::ImmutableUnique(Box::new(Aliasability::NonAliasable));
+        assert!(!unique.is_aliasable());
+        assert_eq!(unique.reason(), None);
+    }
+
+    #[test]
+    fn mutability_category_user_str() {
+        assert_eq!(MutabilityCategory::McDeclared.to_user_str(), "mutable");
+        assert_eq!(MutabilityCategory::McInherited.to_user_str(), "mutable");
+        assert_eq!(MutabilityCategory::McDeclared.to_user_str_verbose(), "declared mutable");
+        assert_eq!(MutabilityCategory::McInherited.to_user_str_verbose(), "inherited mutable");
+        assert_eq!(MutabilityCategory::McImmutable.to_user_str_verbose(), "immutable");
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        round_trip(MutabilityCategory::McImmutable);
+        round_trip(MutabilityCategory::McDeclared);
+        round_trip(MutabilityCategory::McInherited);
+
+        round_trip(InteriorOffsetKind::ConstIndex(3));
+        round_trip(InteriorOffsetKind::RuntimeIndex);
+        round_trip(InteriorOffsetKind::Pattern);
+
+        round_trip(Note::NoteIndex);
+        round_trip(Note::NoteTry);
+        round_trip(Note::NoteMatchGuard);
+        round_trip(Note::NoteNone);
+
+        round_trip(PointerKind::Unique);
+        round_trip(PointerKind::UnsafePtr(hir::MutMutable));
+
+        round_trip(Categorization::StaticItem);
+        round_trip(Categorization::Local(ast::NodeId::new(7)));
+    }
+
+    #[test]
+    fn serialize_round_trip_field_names() {
+        ::syntax::with_globals(|| {
+            let field = FieldIndex(1, Symbol::intern("b"));
+            round_trip(InteriorKind::InteriorField(field));
+            round_trip(InteriorKind::InteriorUnionField(field));
+            round_trip(InteriorKind::InteriorElement(InteriorOffsetKind::Pattern));
+
+            // `FieldIndex` only compares indices, so check the name separately.
+            let mut encoder = opaque::Encoder::new(Vec::new());
+            field.encode(&mut encoder).unwrap();
+            let data = encoder.into_inner();
+            let decoded = FieldIndex::decode(&mut opaque::Decoder::new(&data, 0)).unwrap();
+            assert_eq!(decoded.1, field.1);
+        });
+    }
+}
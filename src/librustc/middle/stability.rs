@@ -13,6 +13,7 @@
 
 pub use self::StabilityLevel::*;
 
+use errors::Applicability;
 use lint;
 use hir::def::Def;
 use hir::def_id::{CrateNum, CRATE_DEF_INDEX, DefId, LOCAL_CRATE};
@@ -23,7 +24,7 @@ use syntax::symbol::Symbol;
 use syntax_pos::{Span, MultiSpan};
 use syntax::ast;
 use syntax::ast::{NodeId, Attribute};
-use syntax::feature_gate::{GateIssue, emit_feature_err};
+use syntax::feature_gate::{GateIssue, emit_feature_err, feature_err};
 use syntax::attr::{self, Stability, Deprecation};
 use util::nodemap::{FxHashSet, FxHashMap};
 
@@ -159,6 +160,12 @@ impl<'a, 'tcx: 'a> Annotator<'a, 'tcx> {
 
                 let stab = self.tcx.intern_stability(stab);
 
+                match stab.level {
+                    attr::Stable { since } => self.check_stable_since(id, item_sp, since),
+                    attr::Unstable { issue: 0, .. } => self.check_unstable_issue(id, attrs),
+                    attr::Unstable { .. } => {}
+                }
+
                 // Check if deprecated_since < stable_since. If it is,
                 // this is *almost surely* an accident.
                 if let (&Some(attr::RustcDeprecation {since: dep_since, ..}),
@@ -245,6 +252,42 @@ impl<'a, 'tcx: 'a> Annotator<'a, 'tcx> {
             }
         }
     }
+
+    // Lint `#[stable(since = "0.x.y")]`: such versions predate the
+    // stability system, so it is unclear what they are meant to convey.
+    // `core` and `std` are exempt, as their stability history is
+    // maintained by hand.
+    fn check_stable_since(&self, id: NodeId, item_sp: Span, since: Symbol) {
+        let krate = self.tcx.crate_name(LOCAL_CRATE);
+        if krate == "core" || krate == "std" {
+            return;
+        }
+        let since = since.as_str();
+        let major = since.split('.').next().and_then(|v| v.parse::<u64>().ok());
+        if major == Some(0) {
+            self.tcx.lint_node(lint::builtin::PRE_1_0_STABILITY_VERSION, id, item_sp,
+                               &format!("stability version `{}` predates Rust 1.0.0", since));
+        }
+    }
+
+    // Lint `#[unstable(issue = "0")]`, the old spelling of `issue = "none"`.
+    // Both parse to the same level, so this has to look at the attribute itself.
+    fn check_unstable_issue(&self, id: NodeId, attrs: &[Attribute]) {
+        let issues = attrs.iter()
+            .filter(|attr| attr.check_name("unstable"))
+            .filter_map(|attr| attr.meta_item_list())
+            .flat_map(|metas| metas)
+            .filter(|meta| meta.check_name("issue"));
+        for meta in issues {
+            if meta.value_str().map_or(false, |issue| issue == "0") {
+                self.tcx.struct_span_lint_node(lint::builtin::UNSTABLE_ISSUE_ZERO, id, meta.span,
+                                               "`issue = \"0\"` is deprecated as a marker for \
+                                                unstable items without a tracking issue")
+                    .help("use `issue = \"none\"` instead")
+                    .emit();
+            }
+        }
+    }
 }
 
 impl<'a, 'tcx> Visitor<'tcx> for Annotator<'a, 'tcx> {
@@ -498,6 +541,45 @@ struct Checker<'a, 'tcx: 'a> {
     tcx: TyCtxt<'a, 'tcx, 'tcx>,
 }
 
+/// Warns about each unstable type named in the type aliased by the stable
+/// type alias `item`, however deeply it is nested, e.g. `Unstable` in
+/// `type Alias = (u32, &'static Unstable);`. Built-in types and type
+/// parameters carry no stability and are always accepted.
+struct AliasedTypeChecker<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    item: &'tcx hir::Item,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for AliasedTypeChecker<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_path(&mut self, path: &'tcx hir::Path, _: HirId) {
+        let aliased_def_id = match path.def {
+            Def::Struct(def_id) | Def::Union(def_id) | Def::Enum(def_id) |
+            Def::TyAlias(def_id) | Def::ForeignTy(def_id) => Some(def_id),
+            _ => None,
+        };
+        let stab = aliased_def_id.and_then(|def_id| self.tcx.lookup_stability(def_id));
+        if let (Some(def_id), Some(stab)) = (aliased_def_id, stab) {
+            if stab.level.is_unstable() {
+                let msg = format!("stable type alias `{}` refers to unstable type `{}`",
+                                  self.item.name,
+                                  self.tcx.item_path_str(def_id));
+                self.tcx.struct_span_lint_node(lint::builtin::UNSTABLE_TYPE_IN_STABLE_ALIAS,
+                                               self.item.id,
+                                               path.span,
+                                               &msg)
+                    .span_label(path.span,
+                                format!("unstable under feature `{}`", stab.feature))
+                    .emit();
+            }
+        }
+        intravisit::walk_path(self, path)
+    }
+}
+
 /// Result of `TyCtxt::eval_stability`.
 pub enum EvalResult {
     /// We can use the item because it is stable or we provided the
@@ -565,7 +647,10 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
             return EvalResult::Allow;
         }
 
-        let lint_deprecated = |def_id: DefId, id: NodeId, note: Option<Symbol>| {
+        let lint_deprecated = |def_id: DefId,
+                               id: NodeId,
+                               note: Option<Symbol>,
+                               replacement: Option<Symbol>| {
             let path = self.item_path_str(def_id);
 
             let msg = if let Some(note) = note {
@@ -574,7 +659,16 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
                 format!("use of deprecated item '{}'", path)
             };
 
-            self.lint_node(lint::builtin::DEPRECATED, id, span, &msg);
+            let mut diag = self.struct_span_lint_node(lint::builtin::DEPRECATED, id, span, &msg);
+            if let Some(replacement) = replacement {
+                diag.span_suggestion_with_applicability(
+                    span,
+                    "replace the use of the deprecated item",
+                    replacement.to_string(),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            diag.emit();
             if id == ast::DUMMY_NODE_ID {
                 span_bug!(span, "emitted a deprecated lint with dummy node id: {:?}", def_id);
             }
@@ -597,7 +691,10 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
                            self.lookup_deprecation_entry(parent_def_id)
                                .map_or(false, |parent_depr| parent_depr.same_origin(&depr_entry));
                 if !skip {
-                    lint_deprecated(def_id, id, depr_entry.attr.note);
+                    lint_deprecated(def_id,
+                                    id,
+                                    depr_entry.attr.note,
+                                    depr_entry.attr.replacement);
                 }
             };
         }
@@ -618,7 +715,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
                 = stability {
             if let Some(id) = id {
                 if deprecation_in_effect(&since.as_str()) {
-                    lint_deprecated(def_id, id, Some(reason));
+                    lint_deprecated(def_id, id, Some(reason), None);
                 }
             }
         }
@@ -679,10 +776,7 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
         match self.eval_stability(def_id, id, span) {
             EvalResult::Allow => {}
             EvalResult::Deny { feature, reason, issue } => {
-                let msg = match reason {
-                    Some(r) => format!("use of unstable library feature '{}': {}", feature, r),
-                    None => format!("use of unstable library feature '{}'", &feature)
-                };
+                let msg = format!("use of unstable library feature '{}'", &feature);
 
                 let msp: MultiSpan = span.into();
                 let cm = &self.sess.parse_sess.source_map();
@@ -702,8 +796,12 @@ impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
                 let error_id = (DiagnosticMessageId::StabilityId(issue), span_key, msg.clone());
                 let fresh = self.sess.one_time_diagnostics.borrow_mut().insert(error_id);
                 if fresh {
-                    emit_feature_err(&self.sess.parse_sess, &feature.as_str(), span,
-                                     GateIssue::Library(Some(issue)), &msg);
+                    let mut err = feature_err(&self.sess.parse_sess, &feature.as_str(), span,
+                                              GateIssue::Library(Some(issue)), &msg);
+                    if let Some(reason) = reason {
+                        err.help(&reason.as_str());
+                    }
+                    err.emit();
                 }
             }
             EvalResult::Unmarked => {
@@ -775,6 +873,17 @@ impl<'a, 'tcx> Visitor<'tcx> for Checker<'a, 'tcx> {
                 }
             }
 
+            // A stable type alias must not make an unstable type reachable
+            // under a stable name, whether the aliased type is local or not.
+            hir::ItemKind::Ty(ref ty, _) => {
+                let def_id = self.tcx.hir.local_def_id(item.id);
+                let alias_is_stable = self.tcx.lookup_stability(def_id)
+                                          .map_or(false, |stab| stab.level.is_stable());
+                if alias_is_stable {
+                    AliasedTypeChecker { tcx: self.tcx, item }.visit_ty(ty);
+                }
+            }
+
             _ => (/* pass */)
         }
         intravisit::walk_item(self, item);
@@ -192,6 +192,7 @@ pub enum DiagnosticMessageId {
     ErrorId(u16), // EXXXX error code as integer
     LintId(lint::LintId),
     StabilityId(u32), // issue number
+    MemCategorizationDump(NodeId), // expression dumped by `-Z dump-mem-categorization`
 }
 
 impl From<&'static lint::Lint> for DiagnosticMessageId {
@@ -0,0 +1,259 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for `#[rustc_category_debug]`: when placed on a `let`
+//! statement, a note showing the `cmt` computed for its initializer is
+//! emitted. For some initializers, the place of interest is not the
+//! initializer itself, so another one is shown instead: the scrutinee of
+//! the match an `e?` expression desugars into (the match itself is always
+//! an rvalue), the assigned place `lhs` of a compound assignment
+//! `lhs op= rhs`, the receiver of a method call, the yielded place of a
+//! `yield`, and each operand of inline assembly.
+//! When placed on a `match` arm, a note is emitted for each binding in
+//! its patterns, showing the `cmt` of the place it binds. If the arm has
+//! a guard, those places are marked with `NoteMatchGuard`.
+//! `#[rustc_category_expect = "..."]` on a `let` statement or a `match`
+//! arm instead checks the categorization of the same places against the
+//! given kinds, e.g. `"field deref local"`, and reports an error if they
+//! differ. Places reached from a local or captured variable are also
+//! rebuilt from their projections with `cat_place_from_mir_like_path`,
+//! and an error is reported if the result is a different place.
+//! Only meant for debugging and testing the compiler.
+
+use rustc::hir::intravisit::{self, Visitor, NestedVisitorMap};
+use rustc::hir::{self, PatKind};
+use rustc::middle::mem_categorization as mc;
+use rustc::middle::mem_categorization::{Categorization, HirNode};
+use rustc::ty;
+use errors::{DiagnosticBuilder, Level};
+use syntax::attr;
+use syntax_pos::Span;
+
+use std::rc::Rc;
+
+use borrowck::BorrowckCtxt;
+
+pub fn check<'a, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>, body: &'tcx hir::Body) {
+    let mc = mc::MemCategorizationContext::new(bccx.tcx,
+                                               &bccx.region_scope_tree,
+                                               bccx.tables,
+                                               Some(bccx.tcx.rvalue_promotable_map(
+                                                   bccx.owner_def_id)));
+    CategoryDumper { bccx, mc }.visit_body(body);
+}
+
+struct CategoryDumper<'a, 'tcx: 'a> {
+    bccx: &'a BorrowckCtxt<'a, 'tcx>,
+    mc: mc::MemCategorizationContext<'a, 'tcx, 'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for CategoryDumper<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        let debug = attr::contains_name(&local.attrs, "rustc_category_debug");
+        let expected = attr::first_attr_value_str_by_name(&local.attrs, "rustc_category_expect");
+        if let Some(ref init) = local.init {
+            if debug || expected.is_some() {
+                for (cmt, msg, span) in self.init_places(init) {
+                    if let Some(expected) = expected {
+                        self.check_expected(&**init, &cmt, &expected.as_str(), span);
+                    }
+                    if debug {
+                        self.dump(&cmt, msg, span);
+                    }
+                }
+            }
+        }
+        intravisit::walk_local(self, local);
+    }
+
+    fn visit_arm(&mut self, arm: &'tcx hir::Arm) {
+        if let Some(expected) = attr::first_attr_value_str_by_name(&arm.attrs,
+                                                                  "rustc_category_expect") {
+            for pat in &arm.pats {
+                pat.walk(|p| {
+                    if let PatKind::Binding(..) = p.node {
+                        if let Ok(cmt) = self.mc.node_to_cmt(p) {
+                            self.check_expected(p, &cmt, &expected.as_str(), p.span);
+                        }
+                    }
+                    true
+                });
+            }
+        }
+        if attr::contains_name(&arm.attrs, "rustc_category_debug") {
+            let mut bindings = vec![];
+            let tcx = self.bccx.tcx;
+            let match_expr = tcx.hir.expect_expr(tcx.hir.get_parent_node(arm.pats[0].id));
+            if let Ok(scrutinee) = self.mc.cat_match_scrutinee(match_expr) {
+                let scrutinee = Rc::new(scrutinee);
+                for pat in &arm.pats {
+                    let _ = self.mc.cat_pattern_in_guard(scrutinee.clone(), arm, pat, |cmt, p| {
+                        if let PatKind::Binding(..) = p.node {
+                            bindings.push(((*cmt).clone(), p.span));
+                        }
+                    });
+                }
+            }
+            for (cmt, span) in bindings {
+                self.dump(&cmt, "cmt of this binding", span);
+            }
+        }
+        intravisit::walk_arm(self, arm);
+    }
+}
+
+impl<'a, 'tcx> CategoryDumper<'a, 'tcx> {
+    /// Returns the places of interest for the `let` initializer `init`,
+    /// along with a description and the span to report them at.
+    fn init_places(&self, init: &hir::Expr) -> Vec<(mc::cmt_<'tcx>, &'static str, Span)> {
+        let place = match init.node {
+            hir::ExprKind::InlineAsm(..) => {
+                let operands = match self.mc.cat_inline_asm_operands(init) {
+                    Ok(operands) => operands,
+                    Err(_) => return vec![],
+                };
+                return operands.into_iter().map(|(cmt, kind)| {
+                    let msg = match kind {
+                        mc::AsmOperandKind::Read => "cmt of this asm input",
+                        mc::AsmOperandKind::Write => "cmt of this asm output",
+                        mc::AsmOperandKind::ReadWrite => "cmt of this asm read-write output",
+                    };
+                    let span = cmt.span;
+                    ((*cmt).clone(), msg, span)
+                }).collect();
+            }
+            hir::ExprKind::Match(_, _, hir::MatchSource::TryDesugar) => {
+                (self.mc.cat_match_scrutinee(init), "cmt of the `?` scrutinee", init.span)
+            }
+            hir::ExprKind::AssignOp(_, ref lhs, _) => {
+                (self.mc.cat_assign_op_lhs(init), "cmt of the assigned place", lhs.span)
+            }
+            hir::ExprKind::Yield(ref value) => {
+                (self.mc.cat_yielded(init), "cmt of the yielded place", value.span)
+            }
+            hir::ExprKind::MethodCall(_, _, ref args) => {
+                let cmt = self.mc.cat_method_receiver(init);
+                let msg = match cmt {
+                    Ok((_, mc::ReceiverMode::ByValue)) => {
+                        "cmt of the method receiver, taken by value"
+                    }
+                    Ok((_, mc::ReceiverMode::ByRef(hir::MutImmutable))) => {
+                        "cmt of the method receiver, taken by `&` reference"
+                    }
+                    _ => "cmt of the method receiver, taken by `&mut` reference",
+                };
+                (cmt.map(|(cmt, _)| cmt), msg, args[0].span)
+            }
+            _ => (self.mc.cat_expr(init), "cmt of this expression", init.span),
+        };
+        match place {
+            (Ok(cmt), msg, span) => vec![(cmt, msg, span)],
+            (Err(_), ..) => vec![],
+        }
+    }
+
+    /// Reports an error at `span` unless `cmt` matches the value of a
+    /// `#[rustc_category_expect]` attribute: the `describe_kind` names of
+    /// the place and of each of its bases, outermost first and separated
+    /// by spaces, e.g. `"field deref local"`. A trailing `..` stands for
+    /// any remaining bases.
+    fn check_expected<N: HirNode>(&self,
+                                  node: &N,
+                                  cmt: &mc::cmt_<'tcx>,
+                                  expected: &str,
+                                  span: Span) {
+        let sess = self.bccx.tcx.sess;
+        let mut kinds = vec![];
+        describe_kinds(cmt, &mut kinds);
+        let mut expected_kinds: Vec<&str> = expected.split_whitespace().collect();
+        let matches = if expected_kinds.last() == Some(&"..") {
+            expected_kinds.pop();
+            kinds.len() >= expected_kinds.len() &&
+                kinds[..expected_kinds.len()] == expected_kinds[..]
+        } else {
+            kinds == expected_kinds
+        };
+        if !matches {
+            sess.span_err(span, &format!("categorized as `{}`, expected `{}`",
+                                         kinds.join(" "),
+                                         expected));
+        }
+        if rebuild(&self.mc, node, cmt).map_or(false, |r| !r.refers_to_same_place_as(cmt)) {
+            sess.span_err(span, "differs from the place rebuilt from its projections");
+        }
+    }
+
+    fn dump(&self, cmt: &mc::cmt_<'tcx>, msg: &str, span: Span) {
+        let tcx = self.bccx.tcx;
+        let tree = cmt.to_debug_tree(tcx).render_tree();
+        let mut note = DiagnosticBuilder::new(tcx.sess.diagnostic(), Level::Note, msg);
+        note.set_span(span);
+        note.note(tree.trim_right());
+        note.emit();
+    }
+}
+
+/// Rebuilds `cmt` from its root and projections with
+/// `cat_place_from_mir_like_path`, if it is reached from a local or a
+/// captured variable through fields, derefs and downcasts only.
+fn rebuild<'a, 'tcx, N: HirNode>(mc: &mc::MemCategorizationContext<'a, 'tcx, 'tcx>,
+                                 node: &N,
+                                 cmt: &mc::cmt_<'tcx>)
+                                 -> Option<mc::cmt<'tcx>> {
+    let mut path = vec![];
+    let root = mir_like_path(cmt, &mut path)?;
+    mc.cat_place_from_mir_like_path(node, root, &path).ok()
+}
+
+fn mir_like_path<'tcx>(cmt: &mc::cmt_<'tcx>,
+                       path: &mut Vec<mc::PathElem<'tcx>>)
+                       -> Option<mc::PathRoot> {
+    if cmt.is_upvar_by_ref() || cmt.is_upvar_by_value() {
+        return upvar_id(cmt).map(mc::PathRoot::Upvar);
+    }
+    let (base, elem) = match cmt.cat {
+        Categorization::Local(id) => return Some(mc::PathRoot::Local(id)),
+        Categorization::Deref(ref b, _) => (b, mc::PathElem::Deref),
+        Categorization::Interior(ref b, mc::InteriorField(field)) |
+        Categorization::Interior(ref b, mc::InteriorUnionField(field)) => {
+            (b, mc::PathElem::Field(field.0, cmt.ty))
+        }
+        Categorization::Downcast(ref b, variant_did, _) => (b, mc::PathElem::Downcast(variant_did)),
+        _ => return None,
+    };
+    let root = mir_like_path(base, path)?;
+    path.push(elem);
+    Some(root)
+}
+
+/// Collects the `describe_kind` names of `cmt` and of each of its bases,
+/// outermost first.
+fn describe_kinds(cmt: &mc::cmt_, kinds: &mut Vec<&'static str>) {
+    kinds.push(cmt.cat.describe_kind());
+    match cmt.cat {
+        Categorization::Deref(ref b, _) |
+        Categorization::Interior(ref b, _) |
+        Categorization::Downcast(ref b, ..) |
+        Categorization::PtrCast(ref b, ..) => describe_kinds(b, kinds),
+        _ => {}
+    }
+}
+
+fn upvar_id(cmt: &mc::cmt_) -> Option<ty::UpvarId> {
+    match cmt.cat {
+        Categorization::Upvar(ref upvar) => Some(upvar.id),
+        Categorization::Deref(ref b, _) => upvar_id(b),
+        _ => None,
+    }
+}
@@ -384,11 +384,13 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
                     }
                 }
 
-                // Borrow of local data must be checked
-                Categorization::Local(..) => true,
+                // Borrow of local data must be checked, and the result of a
+                // pointer cast lives in a local temporary
+                Categorization::Local(..) |
+                Categorization::PtrCast(..) => true,
 
                 // For interior references and downcasts, find out if the base is local
-                Categorization::Downcast(ref cmt_base, _) |
+                Categorization::Downcast(ref cmt_base, _, _) |
                 Categorization::Interior(ref cmt_base, _) => borrow_of_local_data(&cmt_base),
             }
         }
@@ -573,14 +575,14 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
                                                    &old_loan.loan_path));
                     (nl, ol, new_loan_msg, old_loan_msg)
                 } else {
-                    (self.bccx.loan_path_to_string(&new_loan.loan_path),
-                     self.bccx.loan_path_to_string(&old_loan.loan_path),
+                    (new_loan.cmt.cat.display(self.tcx()),
+                     old_loan.cmt.cat.display(self.tcx()),
                      String::new(),
                      String::new())
                 }
             };
 
-            let ol_pronoun = if new_loan.loan_path == old_loan.loan_path {
+            let ol_pronoun = if new_loan.cmt.refers_to_same_place_as(&old_loan.cmt) {
                 "it".to_string()
             } else {
                 format!("`{}`", ol)
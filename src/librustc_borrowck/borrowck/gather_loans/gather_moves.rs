@@ -182,13 +182,15 @@ fn check_and_get_illegal_move_origin<'a, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
         }
 
         Categorization::Rvalue(..) |
+        Categorization::PtrCast(..) |
         Categorization::Local(..) |
         Categorization::Upvar(..) => {
             None
         }
 
-        Categorization::Downcast(ref b, _) |
+        Categorization::Downcast(ref b, _, _) |
         Categorization::Interior(ref b, mc::InteriorField(_)) |
+        Categorization::Interior(ref b, mc::InteriorUnionField(_)) |
         Categorization::Interior(ref b, mc::InteriorElement(Kind::Pattern)) => {
             match b.ty.sty {
                 ty::Adt(def, _) => {
@@ -205,7 +207,8 @@ fn check_and_get_illegal_move_origin<'a, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
             }
         }
 
-        Categorization::Interior(_, mc::InteriorElement(Kind::Index)) => {
+        Categorization::Interior(_, mc::InteriorElement(Kind::ConstIndex(_))) |
+        Categorization::Interior(_, mc::InteriorElement(Kind::RuntimeIndex)) => {
             // Forbid move of arr[i] for arr: [T; 3]; see RFC 533.
             Some(cmt.clone())
         }
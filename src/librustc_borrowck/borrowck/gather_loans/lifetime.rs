@@ -70,6 +70,7 @@ impl<'a, 'tcx> GuaranteeLifetimeContext<'a, 'tcx> {
 
         match cmt.cat {
             Categorization::Rvalue(..) |
+            Categorization::PtrCast(..) |
             Categorization::Local(..) |                     // L-Local
             Categorization::Upvar(..) |
             Categorization::Deref(_, mc::BorrowedPtr(..)) | // L-Deref-Borrowed
@@ -81,7 +82,7 @@ impl<'a, 'tcx> GuaranteeLifetimeContext<'a, 'tcx> {
                 Ok(())
             }
 
-            Categorization::Downcast(ref base, _) |
+            Categorization::Downcast(ref base, _, _) |
             Categorization::Deref(ref base, mc::Unique) |   // L-Deref-Send
             Categorization::Interior(ref base, _) => {      // L-Field
                 self.check(base, discr_scope)
@@ -105,7 +106,8 @@ impl<'a, 'tcx> GuaranteeLifetimeContext<'a, 'tcx> {
         //! rooting etc, and presuming `cmt` is not mutated.
 
         match cmt.cat {
-            Categorization::Rvalue(temp_scope) => {
+            Categorization::Rvalue(temp_scope) |
+            Categorization::PtrCast(_, _, temp_scope) => {
                 temp_scope
             }
             Categorization::Upvar(..) => {
@@ -123,7 +125,7 @@ impl<'a, 'tcx> GuaranteeLifetimeContext<'a, 'tcx> {
             Categorization::Deref(_, mc::BorrowedPtr(_, r)) => {
                 r
             }
-            Categorization::Downcast(ref cmt, _) |
+            Categorization::Downcast(ref cmt, _, _) |
             Categorization::Deref(ref cmt, mc::Unique) |
             Categorization::Interior(ref cmt, _) => {
                 self.scope(cmt)
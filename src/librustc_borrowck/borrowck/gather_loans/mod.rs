@@ -28,6 +28,8 @@ use syntax::ast;
 use syntax_pos::Span;
 use rustc::hir;
 
+use std::rc::Rc;
+
 use self::restrictions::RestrictionResult;
 
 mod lifetime;
@@ -168,26 +170,34 @@ fn check_aliasability<'a, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
                                 req_kind: ty::BorrowKind)
                                 -> Result<(),()> {
 
-    let aliasability = cmt.freely_aliasable();
+    if let Categorization::PtrCast(..) = cmt.cat {
+        // The result of a pointer cast is a fresh temporary, however
+        // aliasable the pointer it was cast from is.
+        return Ok(());
+    }
+
+    let aliasability = cmt.freely_aliasable(bccx.tcx);
     debug!("check_aliasability aliasability={:?} req_kind={:?}",
            aliasability, req_kind);
 
-    match (aliasability, req_kind) {
-        (mc::Aliasability::NonAliasable, _) => {
+    // The contents of an aliasable box (`ImmutableUnique`) are exactly as
+    // aliasable as the box, so only the underlying reason matters here.
+    match (aliasability.reason(), req_kind) {
+        (None, _) => {
             /* Uniquely accessible path -- OK for `&` and `&mut` */
             Ok(())
         }
-        (mc::Aliasability::FreelyAliasable(mc::AliasableStatic), ty::ImmBorrow) => {
+        (Some(mc::AliasableStatic), ty::ImmBorrow) => {
             // Borrow of an immutable static item.
             Ok(())
         }
-        (mc::Aliasability::FreelyAliasable(mc::AliasableStaticMut), _) => {
+        (Some(mc::AliasableStaticMut), _) => {
             // Even touching a static mut is considered unsafe. We assume the
             // user knows what they're doing in these cases.
             Ok(())
         }
-        (mc::Aliasability::FreelyAliasable(alias_cause), ty::UniqueImmBorrow) |
-        (mc::Aliasability::FreelyAliasable(alias_cause), ty::MutBorrow) => {
+        (Some(alias_cause), ty::UniqueImmBorrow) |
+        (Some(alias_cause), ty::MutBorrow) => {
             bccx.report_aliasability_violation(
                         borrow_span,
                         loan_cause,
@@ -210,6 +220,10 @@ fn check_mutability<'a, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
                               -> Result<(),()> {
     debug!("check_mutability(cause={:?} cmt={:?} req_kind={:?}",
            cause, cmt, req_kind);
+    if let Categorization::PtrCast(..) = cmt.cat {
+        // As above, the cast result is a temporary and so always mutable.
+        return Ok(());
+    }
     match req_kind {
         ty::UniqueImmBorrow | ty::ImmBorrow => {
             match cmt.mutbl {
@@ -389,6 +403,7 @@ impl<'a, 'tcx> GatherLoanCtxt<'a, 'tcx> {
                 Loan {
                     index: self.all_loans.len(),
                     loan_path,
+                    cmt: Rc::new(cmt.clone()),
                     kind: req_kind,
                     gen_scope,
                     kill_scope,
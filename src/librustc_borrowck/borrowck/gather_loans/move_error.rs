@@ -101,6 +101,13 @@ fn report_move_errors<'a, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>, errors: &[MoveErr
             err.span_label(bccx.tcx.hir.span(var_node_id),
                            "captured outer variable");
         }
+        match error.move_from.note.overloaded_deref_self_ty().map(|ty| &ty.sty) {
+            Some(&ty::Adt(def, _)) if Some(def.did) == bccx.tcx.lang_items().manually_drop() => {
+                err.help("use `ManuallyDrop::into_inner` to take the value out of the \
+                          `ManuallyDrop`");
+            }
+            _ => {}
+        }
         err.emit();
         bccx.signal_error();
     }
@@ -151,11 +158,12 @@ fn report_cannot_move_out_of<'a, 'tcx>(bccx: &'a BorrowckCtxt<'a, 'tcx>,
         }
         Categorization::Interior(ref b, mc::InteriorElement(ik)) => {
             bccx.cannot_move_out_of_interior_noncopy(
-                move_from.span, b.ty, Some(ik == Kind::Index), Origin::Ast)
+                move_from.span, b.ty, Some(ik != Kind::Pattern), Origin::Ast)
         }
 
-        Categorization::Downcast(ref b, _) |
-        Categorization::Interior(ref b, mc::InteriorField(_)) => {
+        Categorization::Downcast(ref b, _, _) |
+        Categorization::Interior(ref b, mc::InteriorField(_)) |
+        Categorization::Interior(ref b, mc::InteriorUnionField(_)) => {
             match b.ty.sty {
                 ty::Adt(def, _) if def.has_dtor(bccx.tcx) => {
                     bccx.cannot_move_out_of_interior_of_drop(
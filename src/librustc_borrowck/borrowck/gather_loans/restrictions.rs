@@ -61,7 +61,8 @@ impl<'a, 'tcx> RestrictionsContext<'a, 'tcx> {
         let new_lp = |v: LoanPathKind<'tcx>| Rc::new(LoanPath::new(v, cmt.ty));
 
         match cmt.cat.clone() {
-            Categorization::Rvalue(..) => {
+            Categorization::Rvalue(..) |
+            Categorization::PtrCast(..) => {
                 // Effectively, rvalues are stored into a
                 // non-aliasable temporary on the stack. Since they
                 // are inherently non-aliasable, they can only be
@@ -82,7 +83,7 @@ impl<'a, 'tcx> RestrictionsContext<'a, 'tcx> {
                 RestrictionResult::SafeIf(lp.clone(), vec![lp])
             }
 
-            Categorization::Downcast(cmt_base, _) => {
+            Categorization::Downcast(cmt_base, _, _) => {
                 // When we borrow the interior of an enum, we have to
                 // ensure the enum itself is not mutated, because that
                 // could cause the type of the memory to change.
@@ -96,7 +97,7 @@ impl<'a, 'tcx> RestrictionsContext<'a, 'tcx> {
                 // the memory, so no additional restrictions are
                 // needed.
                 let opt_variant_id = match cmt_base.cat {
-                    Categorization::Downcast(_, variant_id) => Some(variant_id),
+                    Categorization::Downcast(_, variant_id, _) => Some(variant_id),
                     _ => None
                 };
                 let interior = interior.cleaned();
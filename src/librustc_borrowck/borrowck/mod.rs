@@ -44,6 +44,7 @@ use std::rc::Rc;
 use rustc_data_structures::sync::Lrc;
 use std::hash::{Hash, Hasher};
 use syntax::ast;
+use syntax::attr;
 use syntax_pos::{MultiSpan, Span};
 use errors::{DiagnosticBuilder, DiagnosticId};
 
@@ -60,6 +61,8 @@ pub mod move_data;
 
 mod unused;
 
+mod category_debug;
+
 #[derive(Clone, Copy)]
 pub struct LoanDataFlowOperator;
 
@@ -152,6 +155,10 @@ fn borrowck<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, owner_def_id: DefId)
         unused::check(&mut bccx, body);
     }
 
+    if tcx.features().rustc_attrs {
+        category_debug::check(&bccx, body);
+    }
+
     Lrc::new(BorrowCheckResult {
         used_mut_nodes: bccx.used_mut_nodes.into_inner(),
         signalled_any_error: bccx.signalled_any_error.into_inner(),
@@ -307,6 +314,8 @@ impl<'a, 'b, 'tcx: 'b> BorrowckErrors<'a> for &'a BorrowckCtxt<'b, 'tcx> {
 pub struct Loan<'tcx> {
     index: usize,
     loan_path: Rc<LoanPath<'tcx>>,
+    /// The place that was borrowed; used to describe it in errors.
+    cmt: mc::cmt<'tcx>,
     kind: ty::BorrowKind,
     restricted_paths: Vec<Rc<LoanPath<'tcx>>>,
 
@@ -376,6 +385,27 @@ impl<'tcx> LoanPath<'tcx> {
     }
 }
 
+impl<'tcx> mc::DisplayPlace for LoanPath<'tcx> {
+    fn display_step(&self, tcx: TyCtxt) -> mc::DisplayStep<Self> {
+        match self.kind {
+            LpVar(id) => mc::DisplayStep::Root(tcx.hir.name(id).to_string()),
+            LpUpvar(ty::UpvarId { var_id: id, closure_expr_id: _ }) => {
+                mc::DisplayStep::Root(tcx.hir.name(tcx.hir.hir_to_node_id(id)).to_string())
+            }
+            LpDowncast(ref lp_base, variant_def_id) => {
+                mc::DisplayStep::Downcast(&**lp_base, variant_def_id)
+            }
+            LpExtend(ref lp_base, _, LpInterior(_, InteriorField(mc::FieldIndex(_, info)))) => {
+                mc::DisplayStep::Field(&**lp_base, info)
+            }
+            LpExtend(ref lp_base, _, LpInterior(_, InteriorElement)) => {
+                mc::DisplayStep::Index(&**lp_base)
+            }
+            LpExtend(ref lp_base, _, LpDeref(_)) => mc::DisplayStep::Deref(&**lp_base),
+        }
+    }
+}
+
 // FIXME (pnkfelix): See discussion here
 // https://github.com/pnkfelix/rust/commit/
 //     b2b39e8700e37ad32b486b9a8409b50a8a53aa51#commitcomment-7892003
@@ -395,7 +425,8 @@ trait ToInteriorKind { fn cleaned(self) -> InteriorKind; }
 impl ToInteriorKind for mc::InteriorKind {
     fn cleaned(self) -> InteriorKind {
         match self {
-            mc::InteriorField(name) => InteriorField(name),
+            // Loan paths tell union fields apart by the type of their base.
+            mc::InteriorField(name) | mc::InteriorUnionField(name) => InteriorField(name),
             mc::InteriorElement(_) => InteriorElement,
         }
     }
@@ -519,6 +550,7 @@ pub fn opt_loan_path_is_field<'tcx>(cmt: &mc::cmt_<'tcx>) -> (Option<Rc<LoanPath
 
     match cmt.cat {
         Categorization::Rvalue(..) |
+        Categorization::PtrCast(..) |
         Categorization::StaticItem => {
             (None, false)
         }
@@ -541,14 +573,14 @@ pub fn opt_loan_path_is_field<'tcx>(cmt: &mc::cmt_<'tcx>) -> (Option<Rc<LoanPath
         Categorization::Interior(ref cmt_base, ik) => {
             (opt_loan_path(cmt_base).map(|lp| {
                 let opt_variant_id = match cmt_base.cat {
-                    Categorization::Downcast(_, did) =>  Some(did),
+                    Categorization::Downcast(_, did, _) =>  Some(did),
                     _ => None
                 };
                 new_lp(LpExtend(lp, cmt.mutbl, LpInterior(opt_variant_id, ik.cleaned())))
             }), true)
         }
 
-        Categorization::Downcast(ref cmt_base, variant_def_id) => {
+        Categorization::Downcast(ref cmt_base, variant_def_id, _) => {
             let lp = opt_loan_path_is_field(cmt_base);
             (lp.0.map(|lp| {
                 new_lp(LpDowncast(lp, variant_def_id))
@@ -566,6 +598,22 @@ pub fn opt_loan_path<'tcx>(cmt: &mc::cmt_<'tcx>) -> Option<Rc<LoanPath<'tcx>>> {
     opt_loan_path_is_field(cmt).0
 }
 
+/// If `cmt` is reached by dereferencing the value of a variable that a
+/// closure captured by reference, returns that variable and the kind of
+/// reference it was captured by.
+fn deref_of_ref_capture<'tcx>(cmt: &mc::cmt_<'tcx>, under_deref: bool)
+                              -> Option<(ty::UpvarId, mc::PointerKind<'tcx>)> {
+    match (&cmt.cat, cmt.note) {
+        (&Categorization::Deref(_, pk), mc::NoteUpvarRef(upvar_id)) => {
+            if under_deref { Some((upvar_id, pk)) } else { None }
+        }
+        (&Categorization::Deref(ref b, _), _) => deref_of_ref_capture(b, true),
+        (&Categorization::Interior(ref b, _), _) |
+        (&Categorization::Downcast(ref b, ..), _) => deref_of_ref_capture(b, under_deref),
+        _ => None,
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Errors
 
@@ -816,25 +864,25 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                         (None, true) => {
                             format!("{} of {} binding",
                                     self.cmt_to_string(&err.cmt),
-                                    err.cmt.mutbl.to_user_str())
+                                    err.cmt.mutbl.to_user_str_verbose())
 
                         }
                         (None, false) => {
                             format!("{} {}",
-                                    err.cmt.mutbl.to_user_str(),
+                                    err.cmt.mutbl.to_user_str_verbose(),
                                     self.cmt_to_string(&err.cmt))
 
                         }
                         (Some(lp), true) => {
                             format!("{} `{}` of {} binding",
-                                    self.cmt_to_string(&err.cmt),
+                                    self.cmt_kind_to_string(&err.cmt),
                                     self.loan_path_to_string(&lp),
-                                    err.cmt.mutbl.to_user_str())
+                                    err.cmt.mutbl.to_user_str_verbose())
                         }
                         (Some(lp), false) => {
                             format!("{} {} `{}`",
-                                    err.cmt.mutbl.to_user_str(),
-                                    self.cmt_to_string(&err.cmt),
+                                    err.cmt.mutbl.to_user_str_verbose(),
+                                    self.cmt_kind_to_string(&err.cmt),
                                     self.loan_path_to_string(&lp))
                         }
                     }
@@ -921,12 +969,27 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                     }
                 }
 
+                // Name the `Deref` impl when the place is only reachable through
+                // its `deref`, since nothing in the source spells out that call.
+                if let Some(impl_did) = err.cmt.immutable_overloaded_deref_impl(self.tcx) {
+                    if let Some(trait_did) = self.tcx.trait_id_of_impl(impl_did) {
+                        db.note(&format!("the dereferenced value is immutable because of the \
+                                          implementation of `{}` for `{}`",
+                                         self.tcx.item_name(trait_did),
+                                         self.tcx.type_of(impl_did)));
+                    }
+                }
+
+                // Point at the matched-on place when the error comes from inside an
+                // enum variant, as the pattern itself may be far away from it.
+                if let Some(discr_span) = err.cmt.downcast_discr_span() {
+                    if discr_span != error_span {
+                        db.span_label(discr_span, "discriminant here");
+                    }
+                }
+
                 self.note_and_explain_mutbl_error(&mut db, &err, &error_span);
-                self.note_immutability_blame(
-                    &mut db,
-                    err.cmt.immutability_blame(),
-                    self.tcx.hir.hir_to_node_id(err.cmt.hir_id)
-                );
+                self.note_immutability_blame(&mut db, &err.cmt);
                 db.emit();
                 self.signal_error();
             }
@@ -940,7 +1003,8 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
 
                 let mut db = self.path_does_not_live_long_enough(error_span, &msg, Origin::Ast);
                 let value_kind = match err.cmt.cat {
-                    mc::Categorization::Rvalue(..) => "temporary value",
+                    mc::Categorization::Rvalue(..) |
+                    mc::Categorization::PtrCast(..) => "temporary value",
                     _ => "borrowed value",
                 };
 
@@ -1105,12 +1169,13 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
         };
 
         match cause {
-            mc::AliasableStaticMut => {
+            mc::AliasableStaticMut | mc::AliasableRawPtr => {
                 // This path cannot occur. `static mut X` is not checked
-                // for aliasability violations.
+                // for aliasability violations, and `freely_aliasable` never
+                // reports raw pointers.
                 span_bug!(span, "aliasability violation for static mut `{}`", prefix)
             }
-            mc::AliasableStatic | mc::AliasableBorrowed => {}
+            mc::AliasableStatic | mc::AliasableBorrowed | mc::AliasableArgument(_) => {}
         };
         let blame = cmt.immutability_blame();
         let mut err = match blame {
@@ -1138,12 +1203,22 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                                                             Origin::Ast)
             }
         };
-        self.note_immutability_blame(
-            &mut err,
-            blame,
-            self.tcx.hir.hir_to_node_id(cmt.hir_id)
-        );
+        if let Some(ImmutabilityBlame::ClosureEnv(_)) = blame {
+            // Name the reference a captured variable was captured by when
+            // the data is behind it, the way the user wrote that reference.
+            if let Some((upvar_id, pk)) = deref_of_ref_capture(cmt, false) {
+                let var_node_id = self.tcx.hir.hir_to_node_id(upvar_id.var_id);
+                err.note(&format!("`{}` is captured by `{}` reference",
+                                  self.tcx.hir.name(var_node_id),
+                                  mc::ptr_sigil_user(pk)));
+            }
+        }
+        self.note_immutability_blame(&mut err, cmt);
 
+        if let mc::AliasableArgument(id) = cause {
+            err.note(&format!("this data is borrowed from the function argument `{}`",
+                              self.tcx.hir.name(id)));
+        }
         if is_closure {
             err.help("closures behind references must be called via `&mut`");
         }
@@ -1222,13 +1297,19 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
 
     fn note_immutability_blame(&self,
                                db: &mut DiagnosticBuilder,
-                               blame: Option<ImmutabilityBlame>,
-                               error_node_id: ast::NodeId) {
-        match blame {
+                               cmt: &mc::cmt_<'tcx>) {
+        let error_node_id = self.tcx.hir.hir_to_node_id(cmt.hir_id);
+        match cmt.immutability_blame() {
             None => {}
             Some(ImmutabilityBlame::ClosureEnv(_)) => {}
             Some(ImmutabilityBlame::ImmLocal(node_id)) => {
-                self.note_immutable_local(db, error_node_id, node_id)
+                self.note_immutable_local(db, cmt, error_node_id, node_id)
+            }
+            Some(ImmutabilityBlame::ImmTupleField(node_id, index)) => {
+                self.note_immutable_local(db, cmt, error_node_id, node_id);
+                db.note(&format!("this is anonymous field `#{}` of immutable tuple `{}`",
+                                 index,
+                                 self.tcx.hir.name(node_id)));
             }
             Some(ImmutabilityBlame::LocalDeref(node_id)) => {
                 match self.local_binding_mode(node_id) {
@@ -1254,6 +1335,14 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                 }
             }
             Some(ImmutabilityBlame::AdtFieldDeref(_, field)) => {
+                // Libraries can explain why a field only gives shared access
+                // with `#[rustc_borrowck_expl = "..."]`.
+                let attrs = self.tcx.get_attrs(field.did);
+                if let Some(expl) = attr::first_attr_value_str_by_name(&attrs,
+                                                                       "rustc_borrowck_expl") {
+                    db.help(&expl.as_str());
+                }
+
                 let node_id = match self.tcx.hir.as_local_node_id(field.did) {
                     Some(node_id) => node_id,
                     None => return
@@ -1265,6 +1354,23 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                     }
                 }
             }
+            Some(ImmutabilityBlame::TupleFieldDeref(node_id, index)) => {
+                let local_ty = match self.local_ty(node_id) {
+                    (Some(local_ty), _) => Some(local_ty),
+                    (None, _) => match self.tcx.hir.get(self.tcx.hir.get_parent_node(node_id)) {
+                        hir_map::Node::NodeLocal(local) => local.ty.as_ref().map(|ty| &**ty),
+                        _ => None,
+                    },
+                };
+
+                if let Some(&hir::Ty { node: hir::TyKind::Tup(ref elems), .. }) = local_ty {
+                    if let Some(elem) = elems.get(index) {
+                        if let Some(msg) = self.suggest_mut_for_immutable(elem, false) {
+                            db.span_label(elem.span, msg);
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -1273,6 +1379,7 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
      // not a mutable reference) or to avoid borrowing altogether
     fn note_immutable_local(&self,
                             db: &mut DiagnosticBuilder,
+                            cmt: &mc::cmt_<'tcx>,
                             borrowed_node_id: ast::NodeId,
                             binding_node_id: ast::NodeId) {
         let let_span = self.tcx.hir.span(binding_node_id);
@@ -1298,9 +1405,15 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                         immutable binding to a mutable reference",
                         snippet
                     );
-                } else {
+                } else if cmt.guarantor().mutbl.requires_mut_keyword() {
+                    // Label the binding from the point where `mut` goes, if known.
+                    let blame = cmt.immutability_blame();
+                    let label_span = match blame.and_then(|b| b.suggestion_span(self.tcx)) {
+                        Some(mut_span) => mut_span.to(let_span),
+                        None => let_span,
+                    };
                     db.span_label(
-                        let_span,
+                        label_span,
                         format!("consider changing this to `mut {}`", snippet),
                     );
                 }
@@ -1387,78 +1500,24 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
             }
         }
     }
-    pub fn append_loan_path_to_string(&self,
-                                      loan_path: &LoanPath<'tcx>,
-                                      out: &mut String) {
-        match loan_path.kind {
-            LpUpvar(ty::UpvarId { var_id: id, closure_expr_id: _ }) => {
-                out.push_str(&self.tcx.hir.name(self.tcx.hir.hir_to_node_id(id)).as_str());
-            }
-            LpVar(id) => {
-                out.push_str(&self.tcx.hir.name(id).as_str());
-            }
-
-            LpDowncast(ref lp_base, variant_def_id) => {
-                out.push('(');
-                self.append_loan_path_to_string(&lp_base, out);
-                out.push_str(DOWNCAST_PRINTED_OPERATOR);
-                out.push_str(&self.tcx.item_path_str(variant_def_id));
-                out.push(')');
-            }
-
-            LpExtend(ref lp_base, _, LpInterior(_, InteriorField(mc::FieldIndex(_, info)))) => {
-                self.append_autoderefd_loan_path_to_string(&lp_base, out);
-                out.push('.');
-                out.push_str(&info.as_str());
-            }
-
-            LpExtend(ref lp_base, _, LpInterior(_, InteriorElement)) => {
-                self.append_autoderefd_loan_path_to_string(&lp_base, out);
-                out.push_str("[..]");
-            }
-
-            LpExtend(ref lp_base, _, LpDeref(_)) => {
-                out.push('*');
-                self.append_loan_path_to_string(&lp_base, out);
-            }
-        }
-    }
-
-    pub fn append_autoderefd_loan_path_to_string(&self,
-                                                 loan_path: &LoanPath<'tcx>,
-                                                 out: &mut String) {
-        match loan_path.kind {
-            LpExtend(ref lp_base, _, LpDeref(_)) => {
-                // For a path like `(*x).f` or `(*x)[3]`, autoderef
-                // rules would normally allow users to omit the `*x`.
-                // So just serialize such paths to `x.f` or x[3]` respectively.
-                self.append_autoderefd_loan_path_to_string(&lp_base, out)
-            }
-
-            LpDowncast(ref lp_base, variant_def_id) => {
-                out.push('(');
-                self.append_autoderefd_loan_path_to_string(&lp_base, out);
-                out.push_str(DOWNCAST_PRINTED_OPERATOR);
-                out.push_str(&self.tcx.item_path_str(variant_def_id));
-                out.push(')');
-            }
-
-            LpVar(..) | LpUpvar(..) | LpExtend(.., LpInterior(..)) => {
-                self.append_loan_path_to_string(loan_path, out)
-            }
-        }
-    }
-
     pub fn loan_path_to_string(&self, loan_path: &LoanPath<'tcx>) -> String {
-        let mut result = String::new();
-        self.append_loan_path_to_string(loan_path, &mut result);
-        result
+        mc::display_place(self.tcx, loan_path)
     }
 
     pub fn cmt_to_string(&self, cmt: &mc::cmt_<'tcx>) -> String {
         cmt.descriptive_string(self.tcx)
     }
 
+    /// Like `cmt_to_string`, but without naming the field for field
+    /// accesses, for use next to a loan path that already names it.
+    fn cmt_kind_to_string(&self, cmt: &mc::cmt_<'tcx>) -> String {
+        match cmt.cat {
+            Categorization::Interior(_, mc::InteriorField(_)) |
+            Categorization::Interior(_, mc::InteriorUnionField(_)) => "field".to_string(),
+            _ => self.cmt_to_string(cmt),
+        }
+    }
+
     pub fn cmt_to_path_or_string(&self, cmt: &mc::cmt_<'tcx>) -> String {
         match opt_loan_path(cmt) {
             Some(lp) => format!("`{}`", self.loan_path_to_string(&lp)),
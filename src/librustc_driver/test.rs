@@ -11,11 +11,13 @@
 //! # Standalone Tests for the Inference Module
 
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::mpsc;
 
 use driver;
 use rustc_lint;
 use rustc_resolve::MakeGlobMap;
+use rustc::middle::mem_categorization as mc;
 use rustc::middle::region;
 use rustc::ty::subst::Subst;
 use rustc::traits::ObligationCause;
@@ -41,6 +43,7 @@ use syntax::symbol::Symbol;
 use syntax_pos::DUMMY_SP;
 
 use rustc::hir;
+use rustc::hir::def_id::DefId;
 
 struct Env<'a, 'gcx: 'a + 'tcx, 'tcx: 'a> {
     infcx: &'a infer::InferCtxt<'a, 'gcx, 'tcx>,
@@ -656,3 +659,789 @@ fn walk_ty_skip_subtree() {
         assert!(expected.is_empty());
     })
 }
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "are not the in-progress tables of `infcx`")]
+fn mem_categorization_with_foreign_tables() {
+    use rustc::middle::mem_categorization::MemCategorizationContext;
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx().global_tcx();
+        let def_id = tcx.hir.local_def_id(ast::CRATE_NODE_ID);
+        tcx.infer_ctxt().with_fresh_in_progress_tables(def_id).enter(|infcx| {
+            // These tables were not created by `infcx`.
+            let tables = ty::TypeckTables::empty(Some(def_id));
+            MemCategorizationContext::with_infer(&infcx, env.region_scope_tree, &tables);
+        });
+    })
+}
+
+/// Builds an immutable cmt with a dummy id and span, for tests that put
+/// categorizations together by hand.
+fn mk_cmt<'tcx>(cat: mc::Categorization<'tcx>,
+                ty: Ty<'tcx>,
+                note: mc::Note<'tcx>)
+                -> mc::cmt<'tcx> {
+    Rc::new(mc::cmt_ {
+        hir_id: hir::DUMMY_HIR_ID,
+        span: DUMMY_SP,
+        cat,
+        mutbl: mc::McImmutable,
+        ty,
+        note,
+    })
+}
+
+/// Builds the id of a dummy variable captured by the closure `closure_def_id`.
+fn mk_upvar_id(closure_def_id: DefId) -> ty::UpvarId {
+    ty::UpvarId { var_id: hir::DUMMY_HIR_ID, closure_expr_id: closure_def_id.to_local() }
+}
+
+/// Builds a `_` pattern with a dummy id and span.
+fn mk_wild_pat() -> hir::Pat {
+    hir::Pat {
+        id: ast::DUMMY_NODE_ID,
+        hir_id: hir::DUMMY_HIR_ID,
+        node: hir::PatKind::Wild,
+        span: DUMMY_SP,
+    }
+}
+
+/// Builds a context for the crate root, with empty typeck tables and no
+/// inference context.
+fn empty_mc<'a, 'e, 'gcx, 'tcx>(env: &'a Env<'e, 'gcx, 'tcx>)
+                                -> mc::MemCategorizationContext<'a, 'gcx, 'gcx> {
+    let tcx = env.tcx().global_tcx();
+    let def_id = tcx.hir.local_def_id(ast::CRATE_NODE_ID);
+    let tables = tcx.alloc_tables(ty::TypeckTables::empty(Some(def_id)));
+    mc::MemCategorizationContext::new(tcx, env.region_scope_tree, tables, None)
+}
+
+#[test]
+fn mem_categorization_root() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorField, NoteNone, Unique, Upvar};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let def_id = tcx.hir.local_def_id(ast::CRATE_NODE_ID);
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+
+        let upvar = Upvar {
+            id: mk_upvar_id(def_id),
+            kind: ty::ClosureKind::Fn,
+        };
+        let roots = vec![
+            Categorization::Local(ast::NodeId::new(1)),
+            Categorization::Upvar(upvar),
+            Categorization::StaticItem,
+            Categorization::Rvalue(tcx.types.re_static),
+        ];
+        for root in roots {
+            let base = mk(root.clone());
+            assert_eq!(base.root().cat, root);
+
+            // `(*(*base).f as Variant)` through a box and a reference.
+            let boxed = mk(Categorization::Deref(base, Unique));
+            let field = FieldIndex(0, Symbol::intern("f"));
+            let interior = mk(Categorization::Interior(boxed, InteriorField(field)));
+            let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+            let borrowed = mk(Categorization::Deref(interior, ptr));
+            let place = mk(Categorization::Downcast(borrowed, def_id, DUMMY_SP));
+            assert_eq!(place.root().cat, root);
+        }
+
+        // `(*ref_x).field`: `root` goes through the reference to the local,
+        // while `guarantor` stops at the deref of the reference.
+        let local = Categorization::Local(ast::NodeId::new(1));
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+        let deref = mk(Categorization::Deref(mk(local.clone()), ptr));
+        let field = FieldIndex(0, Symbol::intern("field"));
+        let place = mk(Categorization::Interior(deref.clone(), InteriorField(field)));
+        assert_eq!(place.root().cat, local);
+        assert_eq!(place.guarantor().cat, deref.cat);
+    })
+}
+
+#[test]
+fn mem_categorization_index_non_indexable() {
+    use rustc::middle::mem_categorization::{Categorization, InteriorOffsetKind, McError};
+    use rustc::middle::mem_categorization::NoteNone;
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx().global_tcx();
+        let mc = empty_mc(&env);
+        let pat = mk_wild_pat();
+
+        // Indexing `*r` where `r: &u32`.
+        let r_ty = tcx.mk_imm_ref(tcx.types.re_static, tcx.types.u32);
+        let r = mk_cmt(Categorization::Local(ast::NodeId::new(1)), r_ty, NoteNone);
+        let elem = mc.cat_slice_elem(&pat, r, tcx.types.u32, InteriorOffsetKind::RuntimeIndex);
+        assert_eq!(elem.err(), Some(McError::NonIndexable(tcx.types.u32)));
+    })
+}
+
+#[test]
+fn mem_categorization_pattern_walk_order() {
+    use rustc::middle::mem_categorization::{BindingFirst, Categorization};
+    use rustc::middle::mem_categorization::{NoteNone, SubPatternFirst};
+    use syntax::ptr::P;
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx().global_tcx();
+        let mc = empty_mc(&env);
+
+        // `x @ _`
+        let pat = hir::Pat {
+            node: hir::PatKind::Binding(hir::BindingAnnotation::Unannotated,
+                                        ast::DUMMY_NODE_ID,
+                                        ast::Ident::from_str("x"),
+                                        Some(P(mk_wild_pat()))),
+            ..mk_wild_pat()
+        };
+        let x = mk_cmt(Categorization::Local(ast::NodeId::new(1)), tcx.types.u32, NoteNone);
+        for &(order, expected) in &[(BindingFirst, ["x", "_"]), (SubPatternFirst, ["_", "x"])] {
+            let mut visited = vec![];
+            mc.cat_pattern(x.clone(), &pat, order, |cmt, p| {
+                assert_eq!(cmt, x);
+                visited.push(match p.node {
+                    hir::PatKind::Binding(..) => "x",
+                    _ => "_",
+                });
+            }).unwrap();
+            assert_eq!(visited, expected);
+        }
+    })
+}
+
+#[test]
+fn mem_categorization_can_move_out() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorField, NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+        let field = |base| {
+            let f = FieldIndex(0, Symbol::intern("f"));
+            mk(Categorization::Interior(base, InteriorField(f)))
+        };
+
+        // `box_s.f` where `box_s: Box<S>`.
+        let box_s = mk(Categorization::Local(ast::NodeId::new(1)));
+        let box_s_f = field(mk(Categorization::Deref(box_s, Unique)));
+        assert!(box_s_f.can_move_out());
+
+        // `ref_s.f` where `ref_s: &S`.
+        let ref_s = mk(Categorization::Local(ast::NodeId::new(2)));
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+        let ref_s_f = field(mk(Categorization::Deref(ref_s, ptr)));
+        assert!(!ref_s_f.can_move_out());
+
+        // `(*box_ref_s).f` where `box_ref_s: Box<&S>`.
+        let box_ref_s = mk(Categorization::Local(ast::NodeId::new(3)));
+        let unboxed = mk(Categorization::Deref(box_ref_s, Unique));
+        assert!(!field(mk(Categorization::Deref(unboxed, ptr))).can_move_out());
+
+        assert!(!field(mk(Categorization::StaticItem)).can_move_out());
+    })
+}
+
+#[test]
+fn mem_categorization_innermost_local() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorField, NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+        let field = |base| {
+            let f = FieldIndex(0, Symbol::intern("f"));
+            mk(Categorization::Interior(base, InteriorField(f)))
+        };
+
+        // `x` and `x.f`.
+        let x_id = ast::NodeId::new(1);
+        let x = mk(Categorization::Local(x_id));
+        assert_eq!(x.innermost_local(), Some(x_id));
+        assert_eq!(field(x).innermost_local(), Some(x_id));
+
+        // `(*box_x).f` where `box_x: Box<S>`.
+        let box_x_id = ast::NodeId::new(2);
+        let box_x = mk(Categorization::Local(box_x_id));
+        let box_x_f = field(mk(Categorization::Deref(box_x, Unique)));
+        assert_eq!(box_x_f.innermost_local(), Some(box_x_id));
+
+        // `(*ref_x).f` where `ref_x: &S`.
+        let ref_x = mk(Categorization::Local(ast::NodeId::new(3)));
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+        let ref_x_f = field(mk(Categorization::Deref(ref_x, ptr)));
+        assert_eq!(ref_x_f.innermost_local(), None);
+    })
+}
+
+#[test]
+fn mem_categorization_crosses_box_deref() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorField, NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+        let field = |base| {
+            let f = FieldIndex(0, Symbol::intern("f"));
+            mk(Categorization::Interior(base, InteriorField(f)))
+        };
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+
+        // `x.f` does not go through any deref.
+        let x = mk(Categorization::Local(ast::NodeId::new(1)));
+        assert!(!field(x).crosses_box_deref());
+
+        // `(*box_x).f` where `box_x: Box<S>`.
+        let box_x = mk(Categorization::Local(ast::NodeId::new(2)));
+        let unboxed = mk(Categorization::Deref(box_x, Unique));
+        assert!(field(unboxed.clone()).crosses_box_deref());
+
+        // `(**box_ref_x).f` where `box_ref_x: Box<&S>`: the box is only
+        // reached through the guarantor, the deref of the reference.
+        assert!(!field(mk(Categorization::Deref(unboxed, ptr))).crosses_box_deref());
+
+        // `(*ref_x).f` where `ref_x: &S`.
+        let ref_x = mk(Categorization::Local(ast::NodeId::new(3)));
+        assert!(!field(mk(Categorization::Deref(ref_x, ptr))).crosses_box_deref());
+    })
+}
+
+#[test]
+fn mem_categorization_raw_ptr_aliasability() {
+    use rustc::middle::mem_categorization::{AliasableRawPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorField, NoteNone, UnsafePtr};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+
+        // `*raw` where `raw: *const S`.
+        let raw = mk(Categorization::Local(ast::NodeId::new(1)));
+        let deref = mk(Categorization::Deref(raw, UnsafePtr(hir::MutImmutable)));
+        assert_eq!(deref.freely_aliasable(tcx).reason(), None);
+        assert_eq!(deref.freely_aliasable_conservative(tcx).reason(), Some(AliasableRawPtr));
+
+        // `(*raw).f` is as aliasable as `*raw`.
+        let f = FieldIndex(0, Symbol::intern("f"));
+        let field = mk(Categorization::Interior(deref, InteriorField(f)));
+        assert_eq!(field.freely_aliasable(tcx).reason(), None);
+        assert_eq!(field.freely_aliasable_conservative(tcx).reason(), Some(AliasableRawPtr));
+    })
+}
+
+#[test]
+fn mem_categorization_describe_kind() {
+    use rustc::middle::mem_categorization::{Categorization, FieldIndex, Upvar};
+    use rustc::middle::mem_categorization::{InteriorElement, InteriorField, InteriorOffsetKind};
+    use rustc::middle::mem_categorization::{InteriorUnionField, NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let def_id = tcx.hir.local_def_id(ast::CRATE_NODE_ID);
+        let base = mk_cmt(Categorization::Local(ast::NodeId::new(1)), tcx.types.u32, NoteNone);
+        let upvar = Upvar {
+            id: mk_upvar_id(def_id),
+            kind: ty::ClosureKind::Fn,
+        };
+        let field = FieldIndex(0, Symbol::intern("f"));
+        let index = InteriorElement(InteriorOffsetKind::RuntimeIndex);
+
+        let cases = vec![
+            (Categorization::Rvalue(tcx.types.re_static), "rvalue"),
+            (Categorization::StaticItem, "static"),
+            (Categorization::Upvar(upvar), "upvar"),
+            (Categorization::Local(ast::NodeId::new(1)), "local"),
+            (Categorization::Deref(base.clone(), Unique), "deref"),
+            (Categorization::Interior(base.clone(), InteriorField(field)), "field"),
+            (Categorization::Interior(base.clone(), InteriorUnionField(field)), "field"),
+            (Categorization::Interior(base.clone(), index), "index"),
+            (Categorization::Downcast(base.clone(), def_id, DUMMY_SP), "downcast"),
+            (Categorization::PtrCast(base.clone(), tcx.types.u32, tcx.types.re_static), "ptr_cast"),
+        ];
+        for (cat, kind) in cases {
+            assert_eq!(cat.describe_kind(), kind);
+        }
+    })
+}
+
+#[test]
+fn mem_categorization_descriptive_string_article() {
+    use rustc::middle::mem_categorization::{Categorization, FieldIndex, Upvar};
+    use rustc::middle::mem_categorization::{BorrowedPtr, InteriorElement, InteriorField};
+    use rustc::middle::mem_categorization::{NoteIndex, NoteNone, Unique, UnsafePtr};
+    use rustc::middle::mem_categorization::InteriorOffsetKind::*;
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let def_id = tcx.hir.local_def_id(ast::CRATE_NODE_ID);
+        let mk = |cat, note| mk_cmt(cat, tcx.types.u32, note);
+        let base = mk(Categorization::Local(ast::NodeId::new(1)), NoteNone);
+        let upvar = Upvar {
+            id: mk_upvar_id(def_id),
+            kind: ty::ClosureKind::Fn,
+        };
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+        let field = FieldIndex(0, Symbol::intern("f"));
+
+        let cases = vec![
+            (Categorization::Rvalue(tcx.types.re_static), NoteNone, "a", "non-place"),
+            (Categorization::StaticItem, NoteNone, "a", "static item"),
+            (Categorization::Upvar(upvar), NoteNone,
+             "a", "captured outer variable in an `Fn` closure"),
+            (Categorization::Local(ast::NodeId::new(1)), NoteNone, "a", "local variable"),
+            (Categorization::Deref(base.clone(), Unique), NoteNone, "a", "`Box` content"),
+            (Categorization::Deref(base.clone(), UnsafePtr(hir::MutImmutable)), NoteNone,
+             "a", "dereference of raw pointer"),
+            (Categorization::Deref(base.clone(), ptr), NoteNone, "a", "borrowed content"),
+            (Categorization::Deref(base.clone(), ptr), NoteIndex, "an", "indexed content"),
+            (Categorization::Interior(base.clone(), InteriorField(field)), NoteNone,
+             "a", "field `f`"),
+            (Categorization::Interior(base.clone(), InteriorElement(ConstIndex(0))), NoteNone,
+             "an", "indexed content"),
+            (Categorization::Interior(base.clone(), InteriorElement(RuntimeIndex)), NoteNone,
+             "an", "indexed content"),
+            (Categorization::Interior(base.clone(), InteriorElement(Pattern)), NoteNone,
+             "a", "pattern-bound indexed content"),
+            (Categorization::Downcast(base.clone(), def_id, DUMMY_SP), NoteNone,
+             "a", "local variable"),
+            (Categorization::PtrCast(base.clone(), tcx.types.u32, tcx.types.re_static), NoteNone,
+             "a", "non-place"),
+        ];
+        for (cat, note, article, descr) in cases {
+            let cmt = mk(cat, note);
+            assert_eq!(cmt.descriptive_string_article(tcx), (article, descr.to_string()));
+        }
+    })
+}
+
+#[test]
+fn mem_categorization_strip_downcasts() {
+    use rustc::middle::mem_categorization::{Categorization, FieldIndex, InteriorField};
+    use rustc::middle::mem_categorization::{NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let variant = tcx.hir.local_def_id(ast::CRATE_NODE_ID);
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+        let field = |base| {
+            let f = FieldIndex(0, Symbol::intern("0"));
+            mk(Categorization::Interior(base, InteriorField(f)))
+        };
+        let x = || mk(Categorization::Local(ast::NodeId::new(1)));
+
+        // `(*(x as Variant).0).0` for a multivariant enum, and `(*x.0).0`
+        // for a univariant one, where the downcast is elided.
+        let multi = field(mk(Categorization::Deref(
+            field(mk(Categorization::Downcast(x(), variant, DUMMY_SP))), Unique)));
+        let uni = field(mk(Categorization::Deref(field(x()), Unique)));
+        assert!(!multi.refers_to_same_place_as(&uni));
+        assert!(multi.strip_downcasts().refers_to_same_place_as(&uni.strip_downcasts()));
+        assert_eq!(multi.strip_downcasts(), uni);
+        assert_eq!(uni.strip_downcasts(), uni);
+    })
+}
+
+#[test]
+fn mem_categorization_field_missing() {
+    use rustc::middle::mem_categorization::{Categorization, McError};
+    use rustc::middle::mem_categorization::NoteNone;
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx().global_tcx();
+        let mc = empty_mc(&env);
+        let pat = mk_wild_pat();
+
+        // `t.1` and `t.2` where `t: (u32, u32)`.
+        let pair = tcx.intern_tup(&[tcx.types.u32, tcx.types.u32]);
+        let t = mk_cmt(Categorization::Local(ast::NodeId::new(1)), pair, NoteNone);
+        let second = mc.cat_field(&pat, t.clone(), 1, ast::Ident::from_str("1"), tcx.types.u32);
+        assert!(second.is_ok());
+        let third = mc.cat_field(&pat, t, 2, ast::Ident::from_str("2"), tcx.types.u32);
+        assert_eq!(third.err(), Some(McError::NoSuchField(pair, 2)));
+    })
+}
+
+#[test]
+fn mem_categorization_common_prefix() {
+    use rustc::middle::mem_categorization::{Categorization, FieldIndex, InteriorField, NoteNone};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+        let field = |base, index, name| {
+            let f = FieldIndex(index, Symbol::intern(name));
+            mk(Categorization::Interior(base, InteriorField(f)))
+        };
+        let x = mk(Categorization::Local(ast::NodeId::new(1)));
+        let y = mk(Categorization::Local(ast::NodeId::new(2)));
+        let x_a = field(x.clone(), 0, "a");
+        let x_b = field(x.clone(), 1, "b");
+        let x_a_b = field(x_a.clone(), 1, "b");
+
+        assert_eq!(x_a.common_prefix(&x_b), Some(x.clone()));
+        assert_eq!(x_a.common_prefix(&x_a_b), Some(x_a.clone()));
+        assert_eq!(x_a_b.common_prefix(&x_a), Some(x_a.clone()));
+        assert_eq!(x.common_prefix(&y), None);
+    })
+}
+
+#[test]
+fn mem_categorization_deep_guarantor() {
+    use rustc::middle::mem_categorization::{Categorization, NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        // `*****...x` through 500 boxes.
+        let x = mk_cmt(Categorization::Local(ast::NodeId::new(1)), tcx.types.u32, NoteNone);
+        let mut place = x.clone();
+        for _ in 0..500 {
+            place = mk_cmt(Categorization::Deref(place, Unique), tcx.types.u32, NoteNone);
+        }
+        assert_eq!(place.guarantor(), *x);
+    })
+}
+
+#[test]
+fn mem_categorization_ptr_cast_guarantor() {
+    use rustc::middle::mem_categorization::{Categorization, NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        // `(*x) as *const u8`: the cast result is a temporary of its own, so
+        // it is its own guarantor and has the scope it was given.
+        let scope = tcx.mk_region(ty::ReScope(region::Scope::Node(hir::ItemLocalId(1))));
+        let x = mk_cmt(Categorization::Local(ast::NodeId::new(1)), tcx.types.u32, NoteNone);
+        let deref = mk_cmt(Categorization::Deref(x.clone(), Unique), tcx.types.u32, NoteNone);
+        let cast = mk_cmt(Categorization::PtrCast(deref, tcx.types.u32, scope),
+                          tcx.types.u32,
+                          NoteNone);
+        assert_eq!(cast.guarantor(), *cast);
+        assert_eq!(cast.rvalue_temp_scope(), Some(scope));
+        assert_eq!(cast.root(), x);
+    })
+}
+
+#[test]
+fn mem_categorization_cat_place() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorElement, InteriorField};
+    use rustc::middle::mem_categorization::McDeclared;
+    use rustc::middle::mem_categorization::InteriorOffsetKind::*;
+    use rustc::mir::{self, Field, LocalDecl, Place, ProjectionElem, Promoted};
+    use rustc_data_structures::indexed_vec::{Idx, IndexVec};
+
+    let source = "#![feature(no_core)] #![no_core] static G: u32 = 0;";
+    test_env(source, errors(&[]), |env| {
+        let tcx = env.tcx().global_tcx();
+        let item = |name: &str| tcx.hir.local_def_id(env.lookup_item(&[name.to_string()]));
+        let mc = empty_mc(&env);
+        let pat = mk_wild_pat();
+
+        // The test environment has no query providers, so there is no
+        // `adt_def` to build structs or enums from: use a tuple instead.
+        let u32_ty = tcx.types.u32;
+        let pair_ty = tcx.intern_tup(&[u32_ty, u32_ty]);
+
+        let mut local_decls = IndexVec::new();
+        local_decls.push(LocalDecl::new_return_place(tcx.mk_nil(), DUMMY_SP));
+        let ref_pair = local_decls.push(
+            LocalDecl::new_temp(tcx.mk_imm_ref(tcx.types.re_static, pair_ty), DUMMY_SP));
+        let array = local_decls.push(LocalDecl::new_temp(tcx.mk_array(u32_ty, 4), DUMMY_SP));
+        let index = local_decls.push(LocalDecl::new_temp(tcx.types.usize, DUMMY_SP));
+        let mir = mir::Mir::new(IndexVec::new(), IndexVec::new(), mir::ClearCrossCrate::Clear,
+                                IndexVec::new(), None, local_decls, 0, vec![], DUMMY_SP);
+        // None of the locals come from a user variable, so they are rvalues.
+        let cat = |place| mc.cat_place(&pat, &mir, &place, &|_| None).unwrap();
+
+        let local = cat(Place::Local(ref_pair));
+        assert_eq!(local.cat, Categorization::Rvalue(tcx.types.re_erased));
+        assert_eq!(local.mutbl, McDeclared);
+
+        let promoted = cat(Place::Promoted(Box::new((Promoted::new(0), u32_ty))));
+        assert_eq!(promoted.cat, Categorization::Rvalue(tcx.types.re_static));
+
+        let statik = cat(Place::Static(Box::new(mir::Static { def_id: item("G"), ty: u32_ty })));
+        assert_eq!(statik.cat, Categorization::StaticItem);
+
+        // `(*ref_pair).1`
+        let deref = cat(Place::Local(ref_pair).deref());
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+        assert_eq!(deref.cat, Categorization::Deref(local.clone(), ptr));
+        let field = cat(Place::Local(ref_pair).deref().field(Field::new(1), u32_ty));
+        let f = FieldIndex(1, Symbol::intern("1"));
+        assert_eq!(field.cat, Categorization::Interior(deref.clone(), InteriorField(f)));
+        assert_eq!(field.descriptive_string(tcx), "field `1`");
+
+        // `array[index]`, `array[2 of 4]`, `array[-1 of 4]` and `array[1:-1]`.
+        let base = cat(Place::Local(array));
+        let elems = vec![
+            (ProjectionElem::Index(index), RuntimeIndex),
+            (ProjectionElem::ConstantIndex { offset: 2, min_length: 4, from_end: false },
+             ConstIndex(2)),
+            (ProjectionElem::ConstantIndex { offset: 1, min_length: 4, from_end: true },
+             Pattern),
+            (ProjectionElem::Subslice { from: 1, to: 1 }, Pattern),
+        ];
+        for (projection, kind) in elems {
+            let elem = cat(Place::Local(array).elem(projection));
+            assert_eq!(elem.cat, Categorization::Interior(base.clone(), InteriorElement(kind)));
+        }
+
+    })
+}
+
+#[test]
+fn mem_categorization_cat_expr_memoized() {
+    use rustc::middle::mem_categorization::{Categorization, MemCategorizationContext};
+
+    let source = "#![feature(no_core)] #![no_core] \
+                  static G: (u32, u32) = (0, 0); fn f() -> u32 { G.0 }";
+    test_env(source, errors(&[]), |env| {
+        let tcx = env.tcx().global_tcx();
+        let f = env.lookup_item(&["f".to_string()]);
+        let body = tcx.hir.body(tcx.hir.body_owned_by(f));
+        let field = match body.value.node {
+            hir::ExprKind::Block(ref block, _) => block.expr.as_ref().unwrap(),
+            ref other => panic!("expected a block, got {:?}", other),
+        };
+        let base = match field.node {
+            hir::ExprKind::Field(ref base, _) => base,
+            ref other => panic!("expected a field access, got {:?}", other),
+        };
+
+        let pair_ty = tcx.intern_tup(&[tcx.types.u32, tcx.types.u32]);
+        let mut tables = ty::TypeckTables::empty(Some(tcx.hir.local_def_id(f)));
+        tables.node_types_mut().insert(field.hir_id, tcx.types.u32);
+        tables.node_types_mut().insert(base.hir_id, pair_ty);
+        tables.field_indices_mut().insert(field.hir_id, 0);
+
+        let base_of = |cmt: &mc::cmt_| match cmt.cat {
+            Categorization::Interior(ref base, _) => base.clone(),
+            ref other => panic!("expected a field, got {:?}", other),
+        };
+
+        // A cache hit hands back the `cmt` recorded by the first call, so
+        // both share the same `Rc` for `G`.
+        let mc = MemCategorizationContext::new(tcx, env.region_scope_tree, &tables, None);
+        let first = mc.cat_expr(field).unwrap();
+        let second = mc.cat_expr(field).unwrap();
+        assert_eq!(base_of(&first).cat, Categorization::StaticItem);
+        assert!(Rc::ptr_eq(&base_of(&first), &base_of(&second)));
+
+        // A copy made with `without_infcx` and a fresh context start out
+        // with empty caches.
+        let copied = mc.without_infcx().cat_expr(field).unwrap();
+        assert_eq!(copied, first);
+        assert!(!Rc::ptr_eq(&base_of(&first), &base_of(&copied)));
+        let mc = MemCategorizationContext::new(tcx, env.region_scope_tree, &tables, None);
+        let third = mc.cat_expr(field).unwrap();
+        assert_eq!(third, first);
+        assert!(!Rc::ptr_eq(&base_of(&first), &base_of(&third)));
+    })
+}
+
+#[test]
+fn mem_categorization_same_place_at_two_spans() {
+    use rustc::middle::mem_categorization::{Categorization, FieldIndex, InteriorField, NoteNone};
+    use syntax_pos::BytePos;
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let f = InteriorField(FieldIndex(0, Symbol::intern("f")));
+        let g = InteriorField(FieldIndex(1, Symbol::intern("g")));
+        let x = mk_cmt(Categorization::Local(ast::NodeId::new(1)), tcx.types.u32, NoteNone);
+        let x_f = mk_cmt(Categorization::Interior(x.clone(), f), tcx.types.u32, NoteNone);
+        let x_g = mk_cmt(Categorization::Interior(x.clone(), g), tcx.types.u32, NoteNone);
+
+        // `x.f` again, written somewhere else.
+        let other_x = mc::cmt_ { span: DUMMY_SP.with_hi(BytePos(1)), ..(*x).clone() };
+        let other_x_f = mc::cmt_ {
+            span: DUMMY_SP.with_hi(BytePos(3)),
+            cat: Categorization::Interior(Rc::new(other_x), f),
+            ..(*x_f).clone()
+        };
+        assert!(*x_f != other_x_f);
+        assert!(x_f.refers_to_same_place_as(&other_x_f));
+        assert!(!x_f.refers_to_same_place_as(&x_g));
+    })
+}
+
+#[test]
+fn mem_categorization_projection_depth() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorElement, InteriorField};
+    use rustc::middle::mem_categorization::{InteriorOffsetKind, NoteNone, Unique};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+        let f = InteriorField(FieldIndex(0, Symbol::intern("f")));
+
+        // `x`, `x.f` and `(*x).f[i]`.
+        let x = mk(Categorization::Local(ast::NodeId::new(1)));
+        assert_eq!(x.projection_depth(), 0);
+        assert_eq!(mk(Categorization::Interior(x.clone(), f)).projection_depth(), 1);
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+        let deref = mk(Categorization::Deref(x.clone(), ptr));
+        let field = mk(Categorization::Interior(deref, f));
+        let index = InteriorElement(InteriorOffsetKind::RuntimeIndex);
+        let elem = mk(Categorization::Interior(field, index));
+        assert_eq!(elem.projection_depth(), 3);
+
+        // Casts are not counted, derefs of boxes are.
+        let cast = mk(Categorization::PtrCast(x.clone(), tcx.types.u32, tcx.types.re_static));
+        assert_eq!(cast.projection_depth(), 0);
+        assert_eq!(mk(Categorization::Deref(cast, Unique)).projection_depth(), 1);
+    })
+}
+
+#[test]
+fn mem_categorization_rvalue_temp_scope() {
+    use rustc::middle::mem_categorization::{Categorization, NoteNone};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let promoted = mk_cmt(Categorization::Rvalue(tcx.types.re_static), tcx.types.u32, NoteNone);
+        assert_eq!(promoted.rvalue_temp_scope(), Some(tcx.types.re_static));
+        let scope = tcx.mk_region(ty::ReScope(region::Scope::Node(hir::ItemLocalId(1))));
+        let temp = mk_cmt(Categorization::Rvalue(scope), tcx.types.u32, NoteNone);
+        assert_eq!(temp.rvalue_temp_scope(), Some(scope));
+        let local = mk_cmt(Categorization::Local(ast::NodeId::new(1)), tcx.types.u32, NoteNone);
+        assert_eq!(local.rvalue_temp_scope(), None);
+    })
+}
+
+#[test]
+fn mem_categorization_display_place() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, FieldIndex};
+    use rustc::middle::mem_categorization::{InteriorElement, InteriorField, InteriorOffsetKind};
+    use rustc::middle::mem_categorization::NoteNone;
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let mk = |cat| mk_cmt(cat, tcx.types.u32, NoteNone);
+        let f = InteriorField(FieldIndex(0, Symbol::intern("f")));
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+
+        // Derefs are only written when they are not the base of a
+        // projection, and pointer casts are never written.
+        let s = mk(Categorization::StaticItem);
+        let deref = mk(Categorization::Deref(s, ptr));
+        let field = mk(Categorization::Interior(deref.clone(), f));
+        let index = InteriorElement(InteriorOffsetKind::RuntimeIndex);
+        let elem = mk(Categorization::Interior(field.clone(), index));
+        let cast = mk(Categorization::PtrCast(deref.clone(), tcx.types.u32, tcx.types.re_static));
+        let cases = vec![
+            (deref, "*<static item>"),
+            (field.clone(), "<static item>.f"),
+            (elem, "<static item>.f[..]"),
+            (mk(Categorization::Deref(field, ptr)), "*<static item>.f"),
+            (cast, "*<static item>"),
+        ];
+        for (cmt, expected) in cases {
+            assert_eq!(mc::display_place(tcx, &cmt.cat), expected);
+            assert_eq!(cmt.cat.display(tcx), expected);
+        }
+    })
+}
+
+#[test]
+fn mem_categorization_upvar_capture_mode() {
+    use rustc::middle::mem_categorization::{BorrowedPtr, Categorization, NoteClosureEnv};
+    use rustc::middle::mem_categorization::{NoteNone, NoteUpvarRef, Upvar};
+
+    test_env(EMPTY_SOURCE_STR, errors(&[]), |env| {
+        let tcx = env.tcx();
+        let id = mk_upvar_id(tcx.hir.local_def_id(ast::CRATE_NODE_ID));
+        let upvar = |kind| Categorization::Upvar(Upvar { id, kind });
+        let ptr = BorrowedPtr(ty::ImmBorrow, tcx.types.re_static);
+
+        // In a `FnOnce` closure, a by-value capture is the upvar itself.
+        let by_value = mk_cmt(upvar(ty::ClosureKind::FnOnce), tcx.types.u32, NoteNone);
+        assert!(by_value.is_upvar_by_value());
+        assert!(!by_value.is_upvar_by_ref());
+
+        // In a `Fn` closure, it is reached through the environment pointer,
+        // and a by-reference capture through the captured reference too.
+        let env_ptr = mk_cmt(upvar(ty::ClosureKind::Fn), tcx.types.u32, NoteNone);
+        let by_value = mk_cmt(Categorization::Deref(env_ptr, ptr), tcx.types.u32,
+                              NoteClosureEnv(id));
+        assert!(by_value.is_upvar_by_value());
+        assert!(!by_value.is_upvar_by_ref());
+        let by_ref = mk_cmt(Categorization::Deref(by_value, ptr), tcx.types.u32,
+                            NoteUpvarRef(id));
+        assert!(by_ref.is_upvar_by_ref());
+        assert!(!by_ref.is_upvar_by_value());
+
+        let local = mk_cmt(Categorization::Local(ast::NodeId::new(1)), tcx.types.u32, NoteNone);
+        assert!(!local.is_upvar_by_value());
+        assert!(!local.is_upvar_by_ref());
+    })
+}
+
+#[test]
+fn mem_categorization_cat_place_expr() {
+    use rustc::middle::mem_categorization::{Categorization, MemCategorizationContext};
+    use rustc::ty::adjustment::{Adjust, Adjustment, AutoBorrow, AutoBorrowMutability};
+
+    let source = "#![feature(no_core)] #![no_core] static G: u32 = 0; fn f() -> u32 { G }";
+    test_env(source, errors(&[]), |env| {
+        let tcx = env.tcx().global_tcx();
+        let f = env.lookup_item(&["f".to_string()]);
+        let body = tcx.hir.body(tcx.hir.body_owned_by(f));
+        let expr = match body.value.node {
+            hir::ExprKind::Block(ref block, _) => block.expr.as_ref().unwrap(),
+            ref other => panic!("expected a block, got {:?}", other),
+        };
+
+        let pair_ty = tcx.intern_tup(&[tcx.types.u32, tcx.types.u32]);
+        let ref_pair_ty = tcx.mk_imm_ref(tcx.types.re_static, pair_ty);
+        let deref = |target| Adjustment { kind: Adjust::Deref(None), target };
+        let borrow = Adjustment {
+            kind: Adjust::Borrow(AutoBorrow::Ref(tcx.types.re_static,
+                                                 AutoBorrowMutability::Immutable)),
+            target: ref_pair_ty,
+        };
+        let cat_place_expr = |ty, adjustments| {
+            let mut tables = ty::TypeckTables::empty(Some(tcx.hir.local_def_id(f)));
+            tables.node_types_mut().insert(expr.hir_id, ty);
+            tables.adjustments_mut().insert(expr.hir_id, adjustments);
+            let mc = MemCategorizationContext::new(tcx, env.region_scope_tree, &tables, None);
+            let cmt = mc.cat_expr(expr).map(|cmt| cmt.cat);
+            mc.cat_place_expr(expr).map(|(place, intermediates)| {
+                assert_eq!(cmt, Ok(place.cat.clone()));
+                (place, intermediates)
+            })
+        };
+
+        // `*G`: the unadjusted expression is the only intermediate step.
+        let (place, intermediates) = cat_place_expr(ref_pair_ty, vec![deref(pair_ty)]).unwrap();
+        assert_eq!(intermediates.len(), 1);
+        assert_eq!(intermediates[0].cat, Categorization::StaticItem);
+        assert_eq!(place.cat, Categorization::Deref(intermediates[0].clone(),
+                                                   mc::BorrowedPtr(ty::ImmBorrow,
+                                                                   tcx.types.re_static)));
+
+        // `&*G`: the reborrow is an rvalue, and what it borrows is not
+        // categorized, just as in `cat_expr`.
+        let adjustments = vec![deref(pair_ty), borrow.clone()];
+        let (place, intermediates) = cat_place_expr(ref_pair_ty, adjustments).unwrap();
+        assert!(intermediates.is_empty());
+        match place.cat {
+            Categorization::Rvalue(..) => {}
+            ref other => panic!("expected an rvalue, got {:?}", other),
+        }
+
+        // So a step that cannot be categorized does not make it fail.
+        let adjustments = vec![deref(pair_ty), borrow];
+        assert!(cat_place_expr(tcx.types.u32, adjustments).is_ok());
+    })
+}
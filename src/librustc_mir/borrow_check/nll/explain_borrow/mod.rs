@@ -11,8 +11,17 @@
 use borrow_check::borrow_set::BorrowData;
 use borrow_check::nll::region_infer::Cause;
 use borrow_check::{Context, MirBorrowckCtxt, WriteKind};
-use rustc::mir::{Location, Place, TerminatorKind};
+use rustc::hir;
+use rustc::hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc::mir::{AggregateKind, Constant, Local, Location, Operand, Place, Rvalue};
+use rustc::mir::{StatementKind, TerminatorKind};
+use rustc::mir::visit::Visitor as MirVisitor;
+use rustc::ty::{self, CanonicalTy, RegionVid};
+use rustc::util::nodemap::FxHashSet;
 use rustc_errors::DiagnosticBuilder;
+use syntax::ast;
+use syntax::symbol::keywords;
+use syntax_pos::Span;
 
 mod find_use;
 
@@ -116,11 +125,243 @@ impl<'cx, 'gcx, 'tcx> MirBorrowckCtxt<'cx, 'gcx, 'tcx> {
                         region,
                         "...",
                     );
+                    self.suggest_for_annotated_lifetime(borrow, region_sub, region, err);
+                    self.explain_closure_annotation(borrow, region_sub, region, err);
+                    self.explain_struct_expr_annotation(borrow, region_sub, region, err);
                 }
             }
         }
     }
 
+    /// When a local is borrowed for a named lifetime `'a` that the user
+    /// wrote explicitly in the body (e.g. `a.method::<&'a u32>(&c)`), the
+    /// local can never live long enough: suggest passing it in as an
+    /// argument instead, or dropping the annotation. `region_sub` is the
+    /// region variable of `region`.
+    fn suggest_for_annotated_lifetime(
+        &self,
+        borrow: &BorrowData<'tcx>,
+        region_sub: RegionVid,
+        region: ty::Region<'tcx>,
+        err: &mut DiagnosticBuilder<'_>,
+    ) {
+        let region_name = match *region {
+            ty::ReEarlyBound(ebr) => ebr.name,
+            ty::ReFree(ty::FreeRegion { bound_region: ty::BrNamed(_, name), .. }) => name,
+            _ => return,
+        };
+
+        let local = match borrow.borrowed_place {
+            Place::Local(local) => local,
+            _ => return,
+        };
+        if self.mir.args_iter().any(|arg| arg == local) {
+            return;
+        }
+        let local_name = match self.mir.local_decls[local].name {
+            Some(name) => name,
+            None => return,
+        };
+
+        // Only the annotations checked where one of the constraints forcing
+        // the borrow to outlive `region` was added are to blame.
+        let regioncx = &self.nonlexical_regioncx;
+        let borrow_region_vid = regioncx.to_region_vid(borrow.region);
+        let mut finder = UserTypeFinder { user_tys: vec![] };
+        for location in regioncx.find_outlives_blame_locations(borrow_region_vid, region_sub) {
+            let block = &self.mir[location.block];
+            match block.statements.get(location.statement_index) {
+                Some(stmt) => finder.visit_statement(location.block, stmt, location),
+                None => finder.visit_terminator(location.block, block.terminator(), location),
+            }
+        }
+        let tcx = self.tcx;
+        let annotated = finder.user_tys.iter().any(|user_ty| {
+            tcx.any_free_region_meets(&user_ty.value, |r| *r == *region)
+        });
+        if annotated {
+            err.help(&format!(
+                "consider taking `{}` as an argument instead of a local, \
+                 or removing the explicit lifetime `{}` from the type annotation",
+                local_name, region_name,
+            ));
+        }
+    }
+
+    /// When the borrow is captured by a closure whose parameters or return
+    /// type were annotated with `region` (e.g. `|x: &mut &'static u32| *x = r`
+    /// or `|| -> &'static u32 { r }`), point at the annotation that forces
+    /// the borrow to outlive it, and at the capture if it is not already
+    /// the primary span. `region_sub` is the region variable of `region`.
+    fn explain_closure_annotation(
+        &self,
+        borrow: &BorrowData<'tcx>,
+        region_sub: RegionVid,
+        region: ty::Region<'tcx>,
+        err: &mut DiagnosticBuilder<'_>,
+    ) {
+        let region_name = match *region {
+            ty::ReStatic => keywords::StaticLifetime.name(),
+            ty::ReEarlyBound(ebr) => ebr.name,
+            ty::ReFree(ty::FreeRegion { bound_region: ty::BrNamed(_, name), .. }) => name,
+            _ => return,
+        };
+
+        let holder = match borrow.assigned_place {
+            Place::Local(local) => local,
+            _ => return,
+        };
+
+        // The requirements of a closure are checked where it is created, so
+        // the closure to blame is the one created at the location of a
+        // constraint forcing the borrow to outlive `region`.
+        let regioncx = &self.nonlexical_regioncx;
+        let borrow_region_vid = regioncx.to_region_vid(borrow.region);
+        let closure = regioncx.find_outlives_blame_locations(borrow_region_vid, region_sub)
+            .into_iter()
+            .rev()
+            .filter_map(|location| {
+                let stmt = self.mir[location.block].statements.get(location.statement_index)?;
+                match stmt.kind {
+                    StatementKind::Assign(_, Rvalue::Aggregate(ref kind, ref operands)) => {
+                        match **kind {
+                            AggregateKind::Closure(def_id, _) => Some((def_id, operands)),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            })
+            .next();
+        let (def_id, operands) = match closure {
+            Some(closure) => closure,
+            None => return,
+        };
+        let node_id = match self.tcx.hir.as_local_node_id(def_id) {
+            Some(node_id) => node_id,
+            None => return,
+        };
+        let decl = match self.tcx.hir.expect_expr(node_id).node {
+            hir::ExprKind::Closure(_, ref decl, ..) => decl,
+            _ => return,
+        };
+        let param = decl.inputs.iter().find(|ty| ty_mentions_lifetime(ty, region_name));
+        let (annotation, annotated) = match (param, &decl.output) {
+            (Some(ty), _) => (ty, "closure parameter"),
+            (None, &hir::FunctionRetTy::Return(ref ty))
+                if ty_mentions_lifetime(ty, region_name) => (ty, "closure return type"),
+            _ => return,
+        };
+        err.span_label(
+            annotation.span,
+            format!("{} annotated with `{}` here", annotated, region_name),
+        );
+
+        // The closure either captures the borrow itself, or (when capturing
+        // by reference) a temporary borrowing the variable that holds it.
+        let reborrows: FxHashSet<Local> = self.mir.basic_blocks().iter()
+            .flat_map(|block| &block.statements)
+            .filter_map(|stmt| match stmt.kind {
+                StatementKind::Assign(Place::Local(l), Rvalue::Ref(_, _, Place::Local(src)))
+                    if src == holder => Some(l),
+                _ => None,
+            })
+            .collect();
+        let capture_span = self.tcx.with_freevars(node_id, |freevars| {
+            freevars.iter().zip(operands).filter_map(|(freevar, operand)| match *operand {
+                Operand::Copy(Place::Local(l)) | Operand::Move(Place::Local(l))
+                    if reborrows.contains(&l) => Some(freevar.span),
+                _ => None,
+            }).next()
+        });
+        if let Some(capture_span) = capture_span {
+            err.span_label(capture_span, "borrow captured here by the closure");
+        }
+    }
+
+    /// When the borrow is stored into a struct expression whose type
+    /// arguments were annotated with `region` (e.g.
+    /// `SomeStruct::<&'static u32> { t: &c }`), point at the type argument
+    /// that forces the borrow to outlive it. `region_sub` is the region
+    /// variable of `region`.
+    fn explain_struct_expr_annotation(
+        &self,
+        borrow: &BorrowData<'tcx>,
+        region_sub: RegionVid,
+        region: ty::Region<'tcx>,
+        err: &mut DiagnosticBuilder<'_>,
+    ) {
+        let region_name = match *region {
+            ty::ReStatic => keywords::StaticLifetime.name(),
+            ty::ReEarlyBound(ebr) => ebr.name,
+            ty::ReFree(ty::FreeRegion { bound_region: ty::BrNamed(_, name), .. }) => name,
+            _ => return,
+        };
+
+        let holder = match borrow.assigned_place {
+            Place::Local(local) => local,
+            _ => return,
+        };
+
+        // Only a struct expression built at the location of a constraint
+        // forcing the borrow to outlive `region` is to blame.
+        let regioncx = &self.nonlexical_regioncx;
+        let borrow_region_vid = regioncx.to_region_vid(borrow.region);
+        let struct_span = regioncx.find_outlives_blame_locations(borrow_region_vid, region_sub)
+            .into_iter()
+            .filter_map(|location| {
+                let stmt = self.mir[location.block].statements.get(location.statement_index)?;
+                match stmt.kind {
+                    StatementKind::Assign(_, Rvalue::Aggregate(ref kind, ref operands)) => {
+                        match **kind {
+                            AggregateKind::Adt(_, _, _, Some(_), _) => {}
+                            _ => return None,
+                        }
+                        let stores_holder = operands.iter().any(|operand| match *operand {
+                            Operand::Copy(Place::Local(l)) |
+                            Operand::Move(Place::Local(l)) => l == holder,
+                            _ => false,
+                        });
+                        if stores_holder { Some(stmt.source_info.span) } else { None }
+                    }
+                    _ => None,
+                }
+            })
+            .next();
+        let struct_span = match struct_span {
+            Some(span) => span,
+            None => return,
+        };
+
+        let node_id = match self.tcx.hir.as_local_node_id(self.mir_def_id) {
+            Some(node_id) => node_id,
+            None => return,
+        };
+        let body = self.tcx.hir.body(self.tcx.hir.body_owned_by(node_id));
+        let mut finder = StructExprFinder { span: struct_span, found: None };
+        finder.visit_body(body);
+        let path = match finder.found {
+            Some(path) => path,
+            None => return,
+        };
+
+        let annotation = path.segments.iter()
+            .filter_map(|segment| segment.args.as_ref())
+            .flat_map(|args| args.args.iter())
+            .find(|arg| match **arg {
+                hir::GenericArg::Lifetime(ref lifetime) => {
+                    lifetime.name.ident().name == region_name
+                }
+                hir::GenericArg::Type(ref ty) => ty_mentions_lifetime(ty, region_name),
+            });
+        if let Some(arg) = annotation {
+            err.span_label(
+                arg.span(),
+                format!("type argument annotated with `{}` here", region_name),
+            );
+        }
+    }
+
     /// Check if a borrow location is within a loop.
     fn is_borrow_location_in_loop(
         &self,
@@ -193,3 +434,72 @@ impl<'cx, 'gcx, 'tcx> MirBorrowckCtxt<'cx, 'gcx, 'tcx> {
         false
     }
 }
+
+/// Returns true if the lifetime named `name` is written in `ty`.
+crate fn ty_mentions_lifetime(ty: &hir::Ty, name: ast::Name) -> bool {
+    let mut finder = LifetimeFinder { name, found: false };
+    finder.visit_ty(ty);
+    finder.found
+}
+
+/// Looks for a lifetime named `name` written in a type.
+struct LifetimeFinder {
+    name: ast::Name,
+    found: bool,
+}
+
+impl<'v> Visitor<'v> for LifetimeFinder {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'v> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'v hir::Lifetime) {
+        if lifetime.name.ident().name == self.name {
+            self.found = true;
+        }
+    }
+}
+
+/// Collects the user type annotations checked at a location: those of the
+/// constants it uses and of the struct expression it builds.
+struct UserTypeFinder<'tcx> {
+    user_tys: Vec<CanonicalTy<'tcx>>,
+}
+
+impl<'tcx> MirVisitor<'tcx> for UserTypeFinder<'tcx> {
+    fn visit_constant(&mut self, constant: &Constant<'tcx>, location: Location) {
+        self.user_tys.extend(constant.user_ty);
+        self.super_constant(constant, location);
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        if let Rvalue::Aggregate(ref kind, _) = *rvalue {
+            if let AggregateKind::Adt(_, _, _, Some(user_ty), _) = **kind {
+                self.user_tys.push(user_ty);
+            }
+        }
+        self.super_rvalue(rvalue, location);
+    }
+}
+
+/// Looks for the path of the struct expression written at `span`.
+struct StructExprFinder<'hir> {
+    span: Span,
+    found: Option<&'hir hir::Path>,
+}
+
+impl<'hir> Visitor<'hir> for StructExprFinder<'hir> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'hir> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'hir hir::Expr) {
+        if let hir::ExprKind::Struct(hir::QPath::Resolved(None, ref path), ..) = expr.node {
+            if expr.span == self.span {
+                self.found = Some(&**path);
+                return;
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
@@ -8,17 +8,20 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use borrow_check::nll::explain_borrow::ty_mentions_lifetime;
 use borrow_check::nll::region_infer::{ConstraintIndex, RegionInferenceContext};
 use borrow_check::nll::type_check::Locations;
+use rustc::hir;
 use rustc::hir::def_id::DefId;
 use rustc::infer::error_reporting::nice_region_error::NiceRegionError;
 use rustc::infer::InferCtxt;
 use rustc::mir::{self, Location, Mir, Place, Rvalue, StatementKind, TerminatorKind};
-use rustc::ty::{TyCtxt, RegionVid};
+use rustc::ty::{self, TyCtxt, RegionVid};
 use rustc_data_structures::indexed_vec::IndexVec;
 use rustc_errors::Diagnostic;
 use std::collections::VecDeque;
 use std::fmt;
+use syntax::symbol::keywords;
 use syntax_pos::Span;
 
 mod region_name;
@@ -363,6 +366,10 @@ impl<'tcx> RegionInferenceContext<'tcx> {
             (ConstraintCategory::CallArgument, true, false) =>
                 self.report_escaping_data_error(mir, infcx, mir_def_id, fr, outlived_fr,
                                                 category, span, errors_buffer),
+            (ConstraintCategory::Return, true, false) if infcx.tcx.is_closure(mir_def_id) =>
+                self.report_closure_return_annotation_error(mir, infcx, mir_def_id, fr,
+                                                            outlived_fr, category, span,
+                                                            errors_buffer),
             _ =>
                 self.report_general_error(mir, infcx, mir_def_id, fr, fr_is_local,
                                           outlived_fr, outlived_fr_is_local,
@@ -422,6 +429,80 @@ impl<'tcx> RegionInferenceContext<'tcx> {
         diag.buffer(errors_buffer);
     }
 
+    /// Report an error because a closure returns data borrowed from one of
+    /// its arguments, while its return type is annotated with a named
+    /// lifetime that the argument is not known to outlive. For example:
+    ///
+    /// ```
+    /// let f = |x: &u32| -> &'static u32 { x };
+    /// ```
+    ///
+    /// Falls back to `report_general_error` if the lifetime is not written in
+    /// the return type or the data does not come from a named argument.
+    fn report_closure_return_annotation_error(
+        &self,
+        mir: &Mir<'tcx>,
+        infcx: &InferCtxt<'_, '_, 'tcx>,
+        mir_def_id: DefId,
+        fr: RegionVid,
+        outlived_fr: RegionVid,
+        category: ConstraintCategory,
+        span: Span,
+        errors_buffer: &mut Vec<Diagnostic>,
+    ) {
+        let tcx = infcx.tcx;
+        let region_name = match self.to_error_region(outlived_fr).map(|r| *r) {
+            Some(ty::ReStatic) => Some(keywords::StaticLifetime.name()),
+            Some(ty::ReEarlyBound(ebr)) => Some(ebr.name),
+            Some(ty::ReFree(ty::FreeRegion { bound_region: ty::BrNamed(_, name), .. })) => {
+                Some(name)
+            }
+            _ => None,
+        };
+        let annotation = region_name.and_then(|region_name| {
+            let node_id = tcx.hir.as_local_node_id(mir_def_id)?;
+            match tcx.hir.expect_expr(node_id).node {
+                hir::ExprKind::Closure(_, ref decl, ..) => match decl.output {
+                    hir::FunctionRetTy::Return(ref ty) if ty_mentions_lifetime(ty, region_name) => {
+                        Some((region_name, ty.span))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        });
+        let fr_name_and_span = match self.get_var_name_and_span_for_region(tcx, mir, fr) {
+            Some((Some(name), fr_span)) => Some((name, fr_span)),
+            _ => None,
+        };
+        let ((region_name, annotation_span), (fr_name, fr_span)) =
+            match (annotation, fr_name_and_span) {
+                (Some(annotation), Some(fr_name_and_span)) => (annotation, fr_name_and_span),
+                _ => {
+                    return self.report_general_error(mir, infcx, mir_def_id,
+                                                     fr, true, outlived_fr, false,
+                                                     category, span, errors_buffer);
+                }
+            };
+
+        let mut diag = tcx.sess.struct_span_err(
+            annotation_span,
+            &format!("the return type lifetime `{}` is longer than the lifetime of `{}`",
+                     region_name, fr_name),
+        );
+        diag.span_label(
+            annotation_span,
+            format!("closure return type annotated with `{}` here", region_name),
+        );
+        diag.span_label(
+            fr_span,
+            format!("`{}` is a reference that is only valid in the closure body", fr_name),
+        );
+        diag.span_label(span, format!("`{}` escapes the closure body here", fr_name));
+
+        diag.buffer(errors_buffer);
+    }
+
     fn report_general_error(
         &self,
         mir: &Mir<'tcx>,
@@ -478,6 +559,15 @@ impl<'tcx> RegionInferenceContext<'tcx> {
         r
     }
 
+    // Finds the locations of the constraints forcing `fr1` to outlive
+    // `fr2`, starting from `fr1`.
+    crate fn find_outlives_blame_locations(&self, fr1: RegionVid, fr2: RegionVid) -> Vec<Location> {
+        let (path, _) = self.find_constraint_paths_between_regions(fr1, |r| r == fr2).unwrap();
+        path.iter()
+            .filter_map(|&index| self.constraints[index].locations.from_location())
+            .collect()
+    }
+
     // Finds a good span to blame for the fact that `fr1` outlives `fr2`.
     crate fn find_outlives_blame_span(
         &self,
@@ -12,6 +12,7 @@
 #![feature(alloc_system)]
 #![cfg_attr(not(stage0), feature(nll))]
 #![cfg_attr(not(stage0), feature(infer_outlives_requirements))]
+#![cfg_attr(not(stage0), allow(unstable_issue_zero))]
 #![feature(sanitizer_runtime)]
 #![feature(staged_api)]
 #![no_std]
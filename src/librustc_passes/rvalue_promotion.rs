@@ -718,7 +718,8 @@ impl<'a, 'gcx, 'tcx> euv::Delegate<'tcx> for CheckCrateVisitor<'a, 'gcx> {
         let mut cur = cmt;
         loop {
             match cur.cat {
-                Categorization::Rvalue(..) => {
+                Categorization::Rvalue(..) |
+                Categorization::PtrCast(..) => {
                     if loan_cause == euv::MatchDiscriminant {
                         // Ignore the dummy immutable borrow created by EUV.
                         break;
@@ -732,7 +733,7 @@ impl<'a, 'gcx, 'tcx> euv::Delegate<'tcx> for CheckCrateVisitor<'a, 'gcx> {
                     break;
                 }
                 Categorization::Deref(ref cmt, _) |
-                Categorization::Downcast(ref cmt, _) |
+                Categorization::Downcast(ref cmt, _, _) |
                 Categorization::Interior(ref cmt, _) => {
                     cur = cmt;
                 }
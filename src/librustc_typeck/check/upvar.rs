@@ -374,7 +374,9 @@ impl<'a, 'gcx, 'tcx> InferBorrowKind<'a, 'gcx, 'tcx> {
                             var_name(tcx, upvar_id.var_id),
                         );
                     }
-                    mc::NoteIndex | mc::NoteNone => {}
+                    mc::NoteIndex | mc::NoteYield | mc::NoteTry |
+                    mc::NoteDeref(..) | mc::NoteConstPromotion | mc::NoteMatchGuard |
+                    mc::NoteNone => {}
                 }
             }
             _ => {}
@@ -390,7 +392,7 @@ impl<'a, 'gcx, 'tcx> InferBorrowKind<'a, 'gcx, 'tcx> {
         match cmt.cat.clone() {
             Categorization::Deref(base, mc::Unique) |
             Categorization::Interior(base, _) |
-            Categorization::Downcast(base, _) => {
+            Categorization::Downcast(base, _, _) => {
                 // Interior or owned data is mutable if base is
                 // mutable, so iterate to the base.
                 self.adjust_upvar_borrow_kind_for_mut(&base);
@@ -409,6 +411,7 @@ impl<'a, 'gcx, 'tcx> InferBorrowKind<'a, 'gcx, 'tcx> {
             Categorization::Deref(_, mc::UnsafePtr(..)) |
             Categorization::StaticItem |
             Categorization::Rvalue(..) |
+            Categorization::PtrCast(..) |
             Categorization::Local(_) |
             Categorization::Upvar(..) => {
                 return;
@@ -422,7 +425,7 @@ impl<'a, 'gcx, 'tcx> InferBorrowKind<'a, 'gcx, 'tcx> {
         match cmt.cat.clone() {
             Categorization::Deref(base, mc::Unique) |
             Categorization::Interior(base, _) |
-            Categorization::Downcast(base, _) => {
+            Categorization::Downcast(base, _, _) => {
                 // Interior or owned data is unique if base is
                 // unique.
                 self.adjust_upvar_borrow_kind_for_unique(&base);
@@ -439,6 +442,7 @@ impl<'a, 'gcx, 'tcx> InferBorrowKind<'a, 'gcx, 'tcx> {
             Categorization::Deref(_, mc::UnsafePtr(..)) |
             Categorization::StaticItem |
             Categorization::Rvalue(..) |
+            Categorization::PtrCast(..) |
             Categorization::Local(_) |
             Categorization::Upvar(..) => {}
         }
@@ -488,7 +492,9 @@ impl<'a, 'gcx, 'tcx> InferBorrowKind<'a, 'gcx, 'tcx> {
 
                 true
             }
-            mc::NoteIndex | mc::NoteNone => false,
+            mc::NoteIndex | mc::NoteYield | mc::NoteTry |
+            mc::NoteDeref(..) | mc::NoteConstPromotion | mc::NoteMatchGuard |
+            mc::NoteNone => false,
         }
     }
 
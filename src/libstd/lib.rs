@@ -274,6 +274,7 @@
 #![feature(never_type)]
 #![cfg_attr(not(stage0), feature(nll))]
 #![cfg_attr(not(stage0), feature(infer_outlives_requirements))]
+#![cfg_attr(not(stage0), allow(unstable_issue_zero))]
 #![feature(exhaustive_patterns)]
 #![feature(on_unimplemented)]
 #![feature(optin_builtin_traits)]
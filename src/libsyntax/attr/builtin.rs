@@ -113,7 +113,8 @@ pub struct Stability {
 /// The available stability levels.
 #[derive(RustcEncodable, RustcDecodable, PartialEq, PartialOrd, Clone, Debug, Eq, Hash)]
 pub enum StabilityLevel {
-    // Reason for the current stability level and the relevant rust-lang issue
+    // Reason for the current stability level and the relevant rust-lang issue,
+    // or 0 if there is none (written `issue = "none"`)
     Unstable { reason: Option<Symbol>, issue: u32 },
     Stable { since: Symbol },
 }
@@ -318,13 +319,15 @@ fn find_stability_generic<'a, I>(diagnostic: &Handler,
                             stab = Some(Stability {
                                 level: Unstable {
                                     reason,
-                                    issue: {
-                                        if let Ok(issue) = issue.as_str().parse() {
-                                            issue
-                                        } else {
-                                            span_err!(diagnostic, attr.span(), E0545,
-                                                      "incorrect 'issue'");
-                                            continue
+                                    issue: match &*issue.as_str() {
+                                        "none" => 0,
+                                        issue => match issue.parse() {
+                                            Ok(issue) => issue,
+                                            Err(_) => {
+                                                span_err!(diagnostic, attr.span(), E0545,
+                                                          "incorrect 'issue'");
+                                                continue
+                                            }
                                         }
                                     }
                                 },
@@ -488,6 +491,8 @@ pub fn eval_condition<F>(cfg: &ast::MetaItem, sess: &ParseSess, eval: &mut F)
 pub struct Deprecation {
     pub since: Option<Symbol>,
     pub note: Option<Symbol>,
+    /// Path of the item to use instead, from `replacement = "..."`.
+    pub replacement: Option<Symbol>,
 }
 
 /// Find the deprecation attribute. `None` if none exists.
@@ -533,16 +538,19 @@ fn find_deprecation_generic<'a, I>(diagnostic: &Handler,
 
             let mut since = None;
             let mut note = None;
+            let mut replacement = None;
             for meta in metas {
                 if let NestedMetaItemKind::MetaItem(ref mi) = meta.node {
                     match &*mi.name().as_str() {
                         "since" => if !get(mi, &mut since) { continue 'outer },
                         "note" => if !get(mi, &mut note) { continue 'outer },
+                        "replacement" => if !get(mi, &mut replacement) { continue 'outer },
                         _ => {
                             handle_errors(
                                 diagnostic,
                                 meta.span,
-                                AttrError::UnknownMetaItem(mi.name(), &["since", "note"]),
+                                AttrError::UnknownMetaItem(mi.name(),
+                                                           &["since", "note", "replacement"]),
                             );
                             continue 'outer
                         }
@@ -553,9 +561,9 @@ fn find_deprecation_generic<'a, I>(diagnostic: &Handler,
                 }
             }
 
-            Some(Deprecation {since: since, note: note})
+            Some(Deprecation {since: since, note: note, replacement: replacement})
         } else {
-            Some(Deprecation{since: None, note: None})
+            Some(Deprecation{since: None, note: None, replacement: None})
         }
     }
 
@@ -499,6 +499,9 @@ declare_features! (
 
     // unsized rvalues at arguments and parameters
     (active, unsized_locals, "1.30.0", Some(48055), None),
+
+    // #[deprecated(replacement = "...")]
+    (active, deprecated_replacement, "1.30.0", None, None),
 );
 
 declare_features! (
@@ -872,6 +875,23 @@ pub const BUILTIN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeG
                                         is just used for rustc unit tests \
                                         and will never be stable",
                                        cfg_fn!(rustc_attrs))),
+    ("rustc_category_debug", Whitelisted, Gated(Stability::Unstable,
+                                       "rustc_attrs",
+                                       "the `#[rustc_category_debug]` attribute \
+                                        is just used for rustc unit tests \
+                                        and will never be stable",
+                                       cfg_fn!(rustc_attrs))),
+    ("rustc_category_expect", Whitelisted, Gated(Stability::Unstable,
+                                       "rustc_attrs",
+                                       "the `#[rustc_category_expect]` attribute \
+                                        is just used for rustc unit tests \
+                                        and will never be stable",
+                                       cfg_fn!(rustc_attrs))),
+    ("rustc_borrowck_expl", Whitelisted, Gated(Stability::Unstable,
+                                       "rustc_attrs",
+                                       "the `#[rustc_borrowck_expl]` attribute \
+                                        is an experimental feature",
+                                       cfg_fn!(rustc_attrs))),
     ("rustc_if_this_changed", Whitelisted, Gated(Stability::Unstable,
                                                  "rustc_attrs",
                                                  "the `#[rustc_if_this_changed]` attribute \
@@ -1524,6 +1544,16 @@ impl<'a> Visitor<'a> for PostExpansionVisitor<'a> {
             }
         }
 
+        if attr.check_name("deprecated") {
+            if let Some(content) = attr.meta_item_list() {
+                if content.iter().any(|c| c.check_name("replacement")) {
+                    gate_feature_post!(&self, deprecated_replacement, attr.span,
+                        "#[deprecated(replacement = \"...\")] is experimental"
+                    );
+                }
+            }
+        }
+
         if !self.context.features.unrestricted_attribute_tokens {
             // Unfortunately, `parse_meta` cannot be called speculatively
             // because it can report errors by itself, so we have to call it
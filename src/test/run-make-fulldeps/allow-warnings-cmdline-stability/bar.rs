@@ -10,6 +10,6 @@
 
 #![crate_type = "lib"]
 #![feature(staged_api)]
-#![unstable(feature = "unstable_test_feature", issue = "0")]
+#![unstable(feature = "unstable_test_feature", issue = "none")]
 
 pub fn baz() {}
@@ -0,0 +1,40 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `Cell` fields can be updated through a shared reference to the struct
+// holding them, including when nested in other fields, since `set` and
+// friends only need `&self`.
+
+use std::cell::Cell;
+
+struct Counter {
+    hits: Cell<u32>,
+}
+
+struct Stats {
+    counter: Counter,
+    total: Cell<u32>,
+}
+
+fn record(stats: &Stats, n: u32) {
+    stats.counter.hits.set(stats.counter.hits.get() + 1);
+    let old = stats.total.replace(0);
+    stats.total.set(old + n);
+}
+
+fn main() {
+    let stats = Stats { counter: Counter { hits: Cell::new(0) }, total: Cell::new(0) };
+    record(&stats, 3);
+    record(&stats, 4);
+    let by_ref = &&stats;
+    by_ref.counter.hits.swap(&by_ref.total);
+    assert_eq!(stats.counter.hits.get(), 7);
+    assert_eq!(stats.total.get(), 2);
+}
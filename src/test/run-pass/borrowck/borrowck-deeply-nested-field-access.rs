@@ -0,0 +1,49 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Exercises categorization of a long chain of overlapping field accesses,
+// mixing plain, boxed and auto-derefed projections, so that borrowck asks
+// for the same subexpressions over and over.
+
+use std::rc::Rc;
+
+struct W<T> { a: T, b: u32 }
+
+type L1 = W<u32>;
+type L2 = W<Box<L1>>;
+type L3 = W<L2>;
+type L4 = W<Rc<L3>>;
+type L5 = W<L4>;
+type L6 = W<Box<L5>>;
+type L7 = W<L6>;
+type L8 = W<L7>;
+
+fn w<T>(a: T, b: u32) -> W<T> { W { a, b } }
+
+fn main() {
+    let mut x: L8 = w(w(w(Box::new(w(w(Rc::new(w(w(Box::new(w(1, 2)), 3), 4)), 5), 6)), 7), 8), 9);
+
+    {
+        let r1 = &x.a.a.a.a.a.a.a.a;
+        let r2 = &x.a.a.a.a.a.a.a.b;
+        let r3 = &x.a.a.a.a.a.a.b;
+        let r4 = &x.a.a.a.a.b;
+        assert_eq!((*r1, *r2, *r3, *r4), (1, 2, 3, 5));
+    }
+
+    {
+        let m = &mut x.a.a.a.a.b;
+        *m += x.a.a.a.a.a.a.a.a + x.a.a.a.a.a.a.a.b;
+    }
+    x.a.a.a.b += x.a.a.a.a.b;
+    assert_eq!(x.a.a.a.a.b, 8);
+    assert_eq!(x.a.a.a.b, 14);
+    assert_eq!(x.b + x.a.b + x.a.a.b, 24);
+}
@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Borrowing through the deref of a `ManuallyDrop` behaves like any other
+// overloaded deref: disjoint shared borrows of the inner value can coexist,
+// and a mutable borrow ends before the value is read again.
+
+use std::mem::ManuallyDrop;
+
+struct Pair { a: String, b: Vec<u32> }
+
+fn main() {
+    let mut pair = ManuallyDrop::new(Pair { a: "a".to_string(), b: vec![1] });
+    {
+        let a = &pair.a;
+        let b = &pair.b;
+        assert_eq!(a, "a");
+        assert_eq!(*b, [1]);
+    }
+    {
+        let b = &mut pair.b;
+        b.push(2);
+    }
+    assert_eq!(pair.b, [1, 2]);
+    let inner: &Pair = &*pair;
+    assert_eq!(inner.a.len(), 1);
+    unsafe { ManuallyDrop::drop(&mut pair); }
+}
@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Assigning through an overloaded `IndexMut` is a mutable place, even
+// when the indexed binding's mode is inferred by default binding modes.
+
+fn set_first(opt: &mut Option<Vec<u32>>, x: u32) {
+    match opt {
+        Some(v) => v[0] = x,
+        None => {}
+    }
+}
+
+fn main() {
+    let mut opt = Some(vec![1, 2, 3]);
+    set_first(&mut opt, 7);
+    assert_eq!(opt, Some(vec![7, 2, 3]));
+
+    let mut pair = (vec![0u32], 1usize);
+    let (ref mut v, i) = pair;
+    v[i - 1] += 5;
+    assert_eq!(pair.0, [5]);
+}
@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Borrowing the result of a raw pointer cast borrows a fresh temporary:
+// it neither requires nor restricts the pointer that was cast.
+
+pub fn main() {
+    let mut x = 22u32;
+    let p: *mut u32 = &mut x;
+
+    let r = &mut (p as *mut u8);
+    let q = p as *const u32;
+    *r = r.wrapping_offset(0);
+    unsafe {
+        assert_eq!(*q, 22);
+        assert_eq!(*(*r as *const u32), 22);
+    }
+
+    let s = &(&x as *const u32 as *const u8);
+    unsafe {
+        assert_eq!(*(*s as *const u32), 22);
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Derefs of raw pointers are freely aliasable, but borrowing through them
+// is unsafe, so borrowck does not report aliasability violations for them.
+
+struct Pair { a: u32, b: u32 }
+
+fn main() {
+    let mut pair = Pair { a: 1, b: 2 };
+    let p: *mut Pair = &mut pair;
+    let q = p;
+    unsafe {
+        let a = &mut (*p).a;
+        let b = &mut (*q).b;
+        *a += 10;
+        *b += 20;
+        let whole = &mut *q;
+        whole.a += 100;
+    }
+    assert_eq!(pair.a, 111);
+    assert_eq!(pair.b, 22);
+}
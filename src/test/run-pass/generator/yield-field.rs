@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Yielding a field moves it out of the generator's upvar.
+
+#![feature(generators, generator_trait)]
+
+use std::ops::{Generator, GeneratorState};
+
+struct S { field: String }
+
+fn main() {
+    let x = S { field: "hi".to_string() };
+    let mut g = move || {
+        yield x.field;
+    };
+    match unsafe { g.resume() } {
+        GeneratorState::Yielded(s) => assert_eq!(s, "hi"),
+        GeneratorState::Complete(()) => panic!(),
+    }
+    match unsafe { g.resume() } {
+        GeneratorState::Complete(()) => {}
+        GeneratorState::Yielded(_) => panic!(),
+    }
+}
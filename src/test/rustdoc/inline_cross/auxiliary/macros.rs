@@ -13,7 +13,7 @@
 #![stable(feature = "rust1", since = "1.0.0")]
 
 /// docs for my_macro
-#[unstable(feature = "macro_test", issue = "0")]
+#[unstable(feature = "macro_test", issue = "none")]
 #[rustc_deprecated(since = "1.2.3", reason = "text")]
 #[macro_export]
 macro_rules! my_macro {
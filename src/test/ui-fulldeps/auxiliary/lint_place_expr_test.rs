@@ -0,0 +1,55 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// force-host
+
+#![feature(plugin_registrar)]
+#![feature(box_syntax, rustc_private)]
+#![cfg_attr(stage0, feature(macro_vis_matcher))]
+#![feature(macro_at_most_once_rep)]
+
+// Load rustc as a plugin to get macros
+#[macro_use]
+extern crate rustc;
+extern crate rustc_plugin;
+
+use rustc::hir;
+use rustc::lint::{LateContext, LintContext, LintPass, LateLintPass, LateLintPassObject, LintArray};
+use rustc::middle::mem_categorization::MemCategorizationContext;
+use rustc_plugin::Registry;
+
+declare_lint!(PLACE_EXPR_LINT, Warn, "Warn about every borrow, naming what it borrows");
+
+struct Pass;
+
+impl LintPass for Pass {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(PLACE_EXPR_LINT)
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Pass {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, e: &'tcx hir::Expr) {
+        if let hir::ExprKind::AddrOf(_, ref place) = e.node {
+            let owner_def_id = cx.tcx.hir.local_def_id(cx.tcx.hir.get_parent(e.id));
+            let desc = MemCategorizationContext::for_lint(cx.tcx, owner_def_id, |mc| {
+                mc.cat_expr(place).map(|cmt| cmt.descriptive_string(cx.tcx))
+            });
+            if let Ok(Ok(desc)) = desc {
+                cx.span_lint(PLACE_EXPR_LINT, e.span, &format!("borrow of {}", desc));
+            }
+        }
+    }
+}
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.register_late_lint_pass(box Pass);
+}
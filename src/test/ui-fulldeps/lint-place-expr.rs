@@ -0,0 +1,34 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// aux-build:lint_place_expr_test.rs
+// ignore-stage1
+#![feature(plugin)]
+#![plugin(lint_place_expr_test)]
+#![allow(dead_code)]
+
+static S: u32 = 0;
+
+struct Foo { bar: u32 }
+
+fn borrows(arg: u32, foo: Foo) {
+    let local = 0u32;
+    let _ = &local; //~ WARNING borrow of local variable
+    let _ = &arg; //~ WARNING borrow of argument
+    let _ = &S; //~ WARNING borrow of static item
+    let _ = &foo.bar; //~ WARNING borrow of field `bar`
+}
+
+#[allow(place_expr_lint)]
+pub fn main() {
+    let local = 0u32;
+    let _ = &local;
+}
@@ -0,0 +1,24 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that data reached through a reference stored behind another
+// reference is not blamed on the function argument holding the outer one.
+
+struct S<'a> {
+    pointer: &'a mut isize
+}
+
+fn d(s: & &S) {
+    *s.pointer += 1; //~ ERROR cannot assign
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that a `&mut` captured uniquely by a closure is reported as
+// captured by `&mut`, not by the internal unique immutable borrow.
+
+fn call<F: Fn()>(f: F) { f() }
+
+fn main() {
+    let mut x = 0;
+    let r = &mut x;
+    call(|| *r += 1); //~ ERROR cannot assign to data in a captured outer variable
+}
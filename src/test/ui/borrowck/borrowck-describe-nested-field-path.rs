@@ -0,0 +1,24 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Conflicting borrows of a nested field are described by the full path.
+
+struct Outer { field: Inner }
+struct Inner { subfield: u32 }
+
+impl Outer {
+    fn f(&mut self) {
+        let a = &mut self.field.subfield;
+        let _b = &mut self.field.subfield; //~ ERROR cannot borrow `self.field.subfield`
+        *a;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a borrowck error reached through an enum downcast points
+// at the discriminant being matched on.
+
+enum Shape {
+    Circle(u32),
+    Rect(u32, u32),
+}
+
+fn grow(shape: Shape) -> u32 {
+    match shape {
+        Shape::Rect(w, h) => w * h,
+        Shape::Circle(ref mut r) => { //~ ERROR cannot borrow
+            *r += 1;
+            *r
+        }
+    }
+}
+
+fn main() {
+    grow(Shape::Circle(1));
+}
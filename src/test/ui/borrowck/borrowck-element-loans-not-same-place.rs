@@ -0,0 +1,21 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that a conflict between borrows of two elements of an array
+// names the element borrowed first instead of calling it "it", since
+// the two elements are not known to be the same place.
+
+fn main() {
+    let mut a = [1, 2];
+    let _r = &a[0];
+    let _m = &mut a[1]; //~ ERROR because `a[..]` is also borrowed
+}
@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Bindings in every alternative of a match arm are categorized, so a
+// move out of borrowed content is reported for each of them.
+
+enum E {
+    A(String),
+    B((u8, String)),
+    C,
+}
+
+fn f(e: &E) {
+    match *e { //~ ERROR cannot move out of borrowed content
+        E::A(s) | E::B((_, s)) => drop(s),
+        E::C => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Moving out of an array is forbidden whether the index is a constant
+// or only known at runtime.
+
+struct NonCopy;
+
+fn main() {
+    let array = [NonCopy, NonCopy, NonCopy];
+    let i = 2;
+    let _a = array[0]; //~ ERROR cannot move out of type `[NonCopy; 3]`, a non-copy array
+    let _b = array[i]; //~ ERROR cannot move out of type `[NonCopy; 3]`, a non-copy array
+}
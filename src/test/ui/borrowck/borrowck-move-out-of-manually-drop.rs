@@ -0,0 +1,19 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Moving out of the deref of a `ManuallyDrop` points at `into_inner`.
+
+use std::mem::ManuallyDrop;
+
+fn main() {
+    let md = ManuallyDrop::new(String::from("hi"));
+    let _s = *md;
+    //~^ ERROR cannot move out of borrowed content
+}
@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Moving out through a `Box` deref is fine; moving out through a
+// reference is not.
+
+#![allow(unused_parens)]
+
+struct S { f: String }
+
+fn main() {
+    let box_x = Box::new(String::new());
+    let _a = *box_x;
+    let box_s = Box::new(S { f: String::new() });
+    let _b = box_s.f;
+    let ref_x = &String::new();
+    let _c = (*ref_x); //~ ERROR cannot move out of borrowed content
+}
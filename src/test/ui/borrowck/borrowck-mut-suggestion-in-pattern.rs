@@ -0,0 +1,21 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that the suggestion to make a binding mutable points at the
+// binding, from where `mut` has to be inserted, also when the binding
+// is nested in a pattern.
+
+fn main() {
+    let (first, second) = (1, 2);
+    let _r = &mut second; //~ ERROR cannot borrow immutable local variable
+    let _s = first;
+}
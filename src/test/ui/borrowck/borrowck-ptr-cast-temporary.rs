@@ -0,0 +1,22 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that borrowing the result of a cast between raw pointer types
+// borrows a temporary, which is dropped at the end of the statement.
+
+fn main() {
+    let x = 0u32;
+    let p = &x as *const u32;
+    let r: &*const u8;
+    r = &(p as *const u8); //~ ERROR borrowed value does not live long enough
+    drop(r);
+}
@@ -0,0 +1,20 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that mutably borrowing a field of an immutable tuple held in a
+// local says which anonymous field of the tuple is borrowed.
+
+fn main() {
+    let t = (1, 2, 3);
+    let r = &mut t.2; //~ ERROR cannot borrow field `t.2` of immutable binding as mutable
+    *r += 1;
+}
@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that assigning through an immutable reference stored in a tuple
+// points at the tuple element's type.
+
+fn in_let() {
+    let a = 1;
+    let b = 2;
+    let t: (&i32, &i32) = (&a, &b);
+    *t.0 = 3; //~ ERROR cannot assign to borrowed content `*t.0` of immutable binding
+    let _ = t.1;
+}
+
+fn in_arg(t: (&i32, &i32)) {
+    *t.1 = 3; //~ ERROR cannot assign to borrowed content `*t.1` of immutable binding
+}
+
+fn main() {
+    in_let();
+    in_arg((&1, &2));
+}
@@ -0,0 +1,42 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+
+// Check that upvar inference copes with moving out of a 512-deep chain
+// of boxes, which it walks with `cmt_::guarantor`.
+
+macro_rules! box2 { ($e:expr) => (Box::new(Box::new($e))) }
+macro_rules! box8 { ($e:expr) => (box2!(box2!(box2!(box2!($e))))) }
+macro_rules! box64 {
+    ($e:expr) => (box8!(box8!(box8!(box8!(box8!(box8!(box8!(box8!($e)))))))))
+}
+macro_rules! box512 {
+    ($e:expr) => (box64!(box64!(box64!(box64!(box64!(box64!(box64!(box64!($e)))))))))
+}
+
+macro_rules! deref2 { ($e:expr) => (**$e) }
+macro_rules! deref8 { ($e:expr) => (deref2!(deref2!(deref2!(deref2!($e))))) }
+macro_rules! deref64 {
+    ($e:expr) => (deref8!(deref8!(deref8!(deref8!(deref8!(deref8!(deref8!(deref8!($e)))))))))
+}
+macro_rules! deref512 {
+    ($e:expr) => (
+        deref64!(deref64!(deref64!(deref64!(deref64!(deref64!(deref64!(deref64!($e))))))))
+    )
+}
+
+fn main() {
+    let x = box512!(String::new());
+    let c = || {
+        let _s: String = deref512!(x);
+    };
+    c();
+}
@@ -0,0 +1,23 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// compile-flags: -Z dump-mem-categorization
+// normalize-stderr-test "local\(\d+\)" -> "local(N)"
+// normalize-stderr-test "rvalue\([^ ]*\)" -> "rvalue(..)"
+
+// Check that `-Z dump-mem-categorization` reports each expression once,
+// even though rvalue promotion and borrowck both categorize it.
+
+fn first(pair: &(u32, u32)) -> u32 {
+    pair.0
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "local\(\d+\)" -> "local(N)"
+
+// Check that the bindings of a `match` arm are categorized as the parts of
+// the scrutinee they bind, whatever their depth in the pattern.
+
+#![feature(rustc_attrs)]
+
+fn bindings(pair: (u32, (u32, u32))) -> u32 {
+    match pair {
+        #[rustc_category_debug]
+        (a, (_, b)) => a + b,
+    }
+}
+
+fn main() {
+    bindings((1, (2, 3)));
+}
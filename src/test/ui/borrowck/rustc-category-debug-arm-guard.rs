@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "local\(\d+\)" -> "local(N)"
+
+// Check that the bindings of a guarded `match` arm are marked as used in
+// the guard, here by a `ref` binding used in an `if let` inside the guard.
+
+#![feature(rustc_attrs)]
+
+fn guarded(pair: (Option<u32>, u32)) -> u32 {
+    match pair {
+        #[rustc_category_debug]
+        (ref opt, _) if { if let Some(x) = *opt { x > 1 } else { false } } => 1,
+        _ => 0,
+    }
+}
+
+fn main() {
+    guarded((Some(2), 3));
+}
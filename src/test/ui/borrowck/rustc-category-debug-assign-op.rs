@@ -0,0 +1,28 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "local\(\d+\)" -> "local(N)"
+
+// Check the categorization of the place assigned by compound assignments.
+
+#![feature(rustc_attrs)]
+
+struct S { field: u32 }
+
+fn main() {
+    let mut s = S { field: 0 };
+    #[rustc_category_debug]
+    let _a = s.field += 1;
+    let p = &mut s.field;
+    #[rustc_category_debug]
+    let _b = *p += 1;
+}
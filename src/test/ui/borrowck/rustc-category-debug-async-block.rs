@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// edition:2018
+// compile-pass
+// compile-flags: -Z borrowck=compare
+// ignore-compare-mode-nll
+// normalize-stderr-test "upvar\(UpvarId\(.*\)/" -> "upvar(UpvarId(..)/"
+
+// Check that a variable mutated inside an `async` block is categorized
+// through the by-ref capture of the generator the block lowers to.
+
+#![feature(async_await, futures_api, rustc_attrs)]
+
+fn main() {
+    let mut x = 0;
+    let _f = async {
+        x += 1;
+        #[rustc_category_debug]
+        let _y = x;
+    };
+}
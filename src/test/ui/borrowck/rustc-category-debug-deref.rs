@@ -0,0 +1,37 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "rvalue\([^ ]*\)" -> "rvalue(..)"
+
+// Check that overloaded derefs, both implicit and explicit, record the
+// `deref` method of the impl they go through.
+
+#![feature(rustc_attrs)]
+
+use std::ops::Deref;
+
+#[derive(Clone, Copy)]
+struct Point { x: u32 }
+struct Wrapper(Point);
+
+impl Deref for Wrapper {
+    type Target = Point;
+    fn deref(&self) -> &Point { &self.0 }
+}
+
+fn main() {
+    let w = Wrapper(Point { x: 1 });
+    #[rustc_category_debug]
+    let _a = w.x;
+    #[rustc_category_debug]
+    let _b = *w;
+}
@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "local\(\d+\)" -> "local(N)"
+
+// Check that the operands of inline assembly are categorized as places,
+// and that outputs are told apart from inputs.
+
+#![feature(asm, rustc_attrs)]
+
+struct S { field: u32 }
+
+fn main() {
+    let mut s = S { field: 0 };
+    let one = 1u32;
+    unsafe {
+        #[rustc_category_debug]
+        let _r = asm!("mov $1, $0" : "=r"(s.field) : "r"(one));
+    }
+    assert_eq!(s.field, 1);
+}
@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "rvalue\([^ ]*\)" -> "rvalue(..)"
+
+// Check that promoted rvalues are marked with `NoteConstPromotion`.
+
+#![feature(rustc_attrs)]
+
+fn main() {
+    let x = 1;
+    #[rustc_category_debug]
+    let _a = &[1, 2, 3];
+    #[rustc_category_debug]
+    let _b = &[x, 2, 3];
+}
@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "rvalue\([^ ]*\)" -> "rvalue(..)"
+
+// Check that the scrutinee of the match that `?` desugars into is marked
+// as coming from the `?`, including when a borrow is live across it.
+
+#![feature(rustc_attrs)]
+
+fn first_plus_len(words: &[String]) -> Result<usize, ()> {
+    let first = &words[0];
+    #[rustc_category_debug]
+    let n = first.parse::<usize>().map_err(|_| ())?;
+    Ok(n + first.len())
+}
+
+fn main() {
+    assert_eq!(first_plus_len(&["12".to_string()]), Ok(14));
+}
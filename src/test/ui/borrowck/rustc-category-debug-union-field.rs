@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "local\(\d+\)" -> "local(N)"
+
+// Check that union fields are categorized as union interiors and can be
+// borrowed inside `unsafe`. Reading them outside of `unsafe` is rejected by
+// the unsafety checker (see `union/union-unsafe.rs`), not by borrowck.
+
+#![feature(rustc_attrs)]
+
+union U { a: u32, b: f32 }
+
+fn main() {
+    let mut u = U { a: 1 };
+    unsafe {
+        #[rustc_category_debug]
+        let _a = u.a;
+        let r = &mut u.b;
+        *r = 2.0;
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "rvalue\([^ ]*\)" -> "rvalue(..)"
+
+// Check that unit structs and variants are categorized as mutable
+// temporaries rather than as static items, so that they can be mutably
+// borrowed and matched on through the borrow.
+
+#![feature(rustc_attrs)]
+
+struct Unit;
+
+fn main() {
+    #[rustc_category_debug]
+    let _a = Unit;
+    #[rustc_category_debug]
+    let _b = None::<u32>;
+    match &mut None::<u32> {
+        Some(x) => *x += 1,
+        None => {}
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+// normalize-stderr-test "local\(\d+\)" -> "local(N)"
+
+// Check that the operand of a `yield` is categorized as the place being
+// yielded, while the `yield` expression itself is the resume value.
+
+#![feature(generators, rustc_attrs)]
+
+struct S { f: u32 }
+
+fn main() {
+    let _gen = || {
+        let mut s = S { f: 1 };
+        s.f += 1;
+        #[rustc_category_debug]
+        let _r = yield s.f;
+    };
+}
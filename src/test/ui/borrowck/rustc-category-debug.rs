@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+
+// Check that `#[rustc_category_debug]` dumps the categorization of a
+// `let` initializer.
+
+#![feature(rustc_attrs)]
+
+struct Point { x: u32, y: u32 }
+struct Line { start: Point, end: Point }
+
+static LINE: Line = Line { start: Point { x: 0, y: 0 }, end: Point { x: 1, y: 1 } };
+
+fn main() {
+    #[rustc_category_debug]
+    let _x = LINE.start.x;
+
+    #[rustc_category_debug]
+    let _y = LINE.end.y;
+}
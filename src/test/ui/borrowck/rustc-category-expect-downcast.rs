@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+
+// Check that a binding in an enum variant pattern is categorized as a field of
+// a downcast.
+
+#![feature(rustc_attrs)]
+
+fn first(opt: Option<u32>) -> u32 {
+    match opt {
+        #[rustc_category_expect = "field downcast local"]
+        Some(ref v) => *v,
+        None => 0,
+    }
+}
+
+fn main() {
+    first(Some(1));
+}
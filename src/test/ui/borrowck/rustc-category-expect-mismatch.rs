@@ -0,0 +1,24 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-compare-mode-nll
+
+// Check that a categorization that does not match the expected one is an error.
+
+#![feature(rustc_attrs)]
+
+fn main() {
+    let x = 1u32;
+    #[rustc_category_expect = "deref local"]
+    let _y = x; //~ ERROR categorized as `local`, expected `deref local`
+    let p = (1u32, 2u32);
+    #[rustc_category_expect = "field deref .."]
+    let _f = p.0; //~ ERROR categorized as `field local`, expected `field deref ..`
+}
@@ -0,0 +1,23 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+
+// Check that a cast between raw pointer types is categorized as a pointer cast.
+
+#![feature(rustc_attrs)]
+
+fn main() {
+    let x = 1u32;
+    let p: *const u32 = &x;
+    #[rustc_category_expect = "ptr_cast local"]
+    let _q = p as *const u8;
+}
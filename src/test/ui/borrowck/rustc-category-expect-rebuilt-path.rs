@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+
+// Check that places built from HIR are the same as the ones rebuilt from
+// their roots and projections with `cat_place_from_mir_like_path`, which
+// `#[rustc_category_expect]` reports an error for otherwise.
+
+#![feature(rustc_attrs)]
+
+struct Inner { x: u32 }
+struct Outer { inner: Box<Inner> }
+
+fn places(outer: &Outer, opt: Option<(u32, u32)>) {
+    #[rustc_category_expect = "field deref field deref local"]
+    let _a = outer.inner.x;
+    match opt {
+        #[rustc_category_expect = "field field downcast local"]
+        Some((_, y)) => { let _ = y; }
+        None => {}
+    }
+}
+
+fn main() {
+    places(&Outer { inner: Box::new(Inner { x: 1 }) }, Some((2, 3)));
+}
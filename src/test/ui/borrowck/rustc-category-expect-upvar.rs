@@ -0,0 +1,27 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// ignore-compare-mode-nll
+
+// Check that a variable captured by value by a `FnOnce` closure is categorized
+// as an upvar.
+
+#![feature(rustc_attrs)]
+
+fn main() {
+    let x = String::new();
+    let c = move || {
+        #[rustc_category_expect = "upvar"]
+        let y = x;
+        drop(y);
+    };
+    c();
+}
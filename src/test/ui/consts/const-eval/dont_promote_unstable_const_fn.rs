@@ -11,7 +11,7 @@
 #![unstable(feature = "humans",
             reason = "who ever let humans program computers,
             we're apparently really bad at it",
-            issue = "0")]
+            issue = "none")]
 
 #![feature(rustc_const_unstable, const_fn)]
 #![feature(staged_api)]
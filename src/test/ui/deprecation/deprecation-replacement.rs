@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+
+#![feature(deprecated_replacement)]
+
+mod old {
+    #[deprecated(since = "1.0.0", note = "use `new::bar` instead", replacement = "new::bar")]
+    pub fn foo() {}
+
+    #[deprecated(since = "1.0.0")]
+    pub fn baz() {}
+}
+
+mod new {
+    pub fn bar() {}
+}
+
+fn main() {
+    old::foo(); //~ WARNING use of deprecated item 'old::foo'
+    old::baz(); //~ WARNING use of deprecated item 'old::baz'
+    new::bar();
+}
@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a stable feature declared with a different `since` version
+// than before is reported, pointing at its first declaration.
+
+#![stable(feature = "stable_test_feature", since = "1.0.0")]
+
+#![feature(staged_api)]
+
+#[stable(feature = "foo", since = "1.0.0")]
+pub fn foo_a() {}
+
+#[stable(feature = "foo", since = "1.0.0")]
+pub fn foo_b() {}
+
+#[stable(feature = "foo_mod", since = "1.0.0")]
+pub mod m {
+    #[stable(feature = "foo", since = "1.5.0")]
+    //~^ ERROR feature `foo` is declared stable since 1.5.0
+    pub fn foo_c() {}
+}
+
+fn main() {}
@@ -11,24 +11,24 @@
 #![feature(staged_api, allow_internal_unstable)]
 #![stable(feature = "stable", since = "1.0.0")]
 
-#[unstable(feature = "function", issue = "0")]
+#[unstable(feature = "function", issue = "none")]
 pub fn unstable() {}
 
 
 #[stable(feature = "stable", since = "1.0.0")]
 pub struct Foo {
-    #[unstable(feature = "struct_field", issue = "0")]
+    #[unstable(feature = "struct_field", issue = "none")]
     pub x: u8
 }
 
 impl Foo {
-    #[unstable(feature = "method", issue = "0")]
+    #[unstable(feature = "method", issue = "none")]
     pub fn method(&self) {}
 }
 
 #[stable(feature = "stable", since = "1.0.0")]
 pub struct Bar {
-    #[unstable(feature = "struct2_field", issue = "0")]
+    #[unstable(feature = "struct2_field", issue = "none")]
     pub x: u8
 }
 
@@ -9,5 +9,5 @@
 // except according to those terms.
 
 #![crate_type = "lib"]
-#![unstable(feature = "issue_52489_unstable", issue = "0")]
+#![unstable(feature = "issue_52489_unstable", issue = "none")]
 #![feature(staged_api)]
@@ -0,0 +1,34 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a lifetime written on a closure parameter is enforced
+// against the variables the closure captures, and that the error
+// points at the annotation.
+
+#![feature(nll)]
+
+fn annot_static_param_ok() {
+    let c = 66;
+    let r = &c;
+    let _closure = |x: &'static u32| {
+        let _y = r;
+        let _z = x;
+    };
+}
+
+fn annot_static_param() {
+    let c = 66;
+    let r = &c; //~ ERROR
+    let _closure = |x: &mut &'static u32| {
+        *x = r;
+    };
+}
+
+fn main() { }
@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a lifetime written on a closure return type is enforced
+// against the closure's arguments and the variables it captures, and
+// that the error points at the annotation.
+
+#![feature(nll)]
+
+fn annot_static_return_ok() {
+    let _closure = |x: &'static u32| -> &'static u32 { x };
+}
+
+fn annot_static_return_arg() {
+    let _closure = |x: &u32| -> &'static u32 { x }; //~ ERROR
+}
+
+fn annot_static_return_capture() {
+    let c = 66;
+    let r = &c; //~ ERROR
+    let _closure = || -> &'static u32 { r };
+}
+
+fn main() { }
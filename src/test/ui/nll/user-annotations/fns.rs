@@ -57,4 +57,10 @@ fn annot_reference_named_lifetime_in_closure_ok<'a>(c: &'a u32) {
     };
 }
 
+fn annot_reference_named_lifetime_unrelated<'a>(d: &'a u32) -> &'a u32 {
+    let c = 66;
+    some_fn::<&'a u32>(d);
+    &c //~ ERROR
+}
+
 fn main() { }
@@ -78,4 +78,23 @@ fn annot_reference_named_lifetime_in_closure_ok<'a>(c: &'a u32) {
     };
 }
 
+fn takes_ref(_: &u32) { }
+
+fn returns_ref(x: &u32) -> &u32 { x }
+
+// `for<'b> &'b u32` is not a type, so higher-ranked annotations can only
+// be tested through fn pointers (or trait objects).
+
+fn annot_higher_ranked_fn() {
+    let a = 22;
+    let b = 44;
+    a.method::<for<'b> fn(&'b u32)>(b,  takes_ref); // OK
+}
+
+fn annot_higher_ranked_fn_with_return() {
+    let a = 22;
+    let b = 44;
+    a.method::<for<'b> fn(&'b u32) -> &'b u32>(b,  returns_ref); // OK
+}
+
 fn main() { }
@@ -0,0 +1,43 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that lifetimes written in the type arguments of a struct
+// expression are enforced, and that errors point at them.
+
+#![feature(nll)]
+
+struct Foo<T> { field: T }
+
+fn annot_too_long() {
+    let local = 22;
+    Foo::<&'static u32> { field: &local }; //~ ERROR
+}
+
+fn annot_too_long_named<'a>(_d: &'a u32) {
+    let local = 22;
+    let _foo = Foo::<&'a u32> { field: &local }; //~ ERROR
+}
+
+fn annot_too_short<'a>(_d: &'a u32, x: &'static u32) -> &'static u32 {
+    let foo = Foo::<&'a u32> { field: x };
+    foo.field //~ ERROR
+}
+
+fn annot_exact<'a>(x: &'a u32) -> &'a u32 {
+    let foo = Foo::<&'a u32> { field: x };
+    foo.field
+}
+
+fn annot_exact_static() -> &'static u32 {
+    static VALUE: u32 = 22;
+    Foo::<&'static u32> { field: &VALUE }.field
+}
+
+fn main() { }
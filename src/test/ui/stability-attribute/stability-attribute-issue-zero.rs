@@ -0,0 +1,28 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+
+// `issue = "none"` marks unstable items without a tracking issue; the old
+// `issue = "0"` spelling still works but is deprecated.
+
+#![feature(staged_api)]
+#![stable(feature = "stable_test_feature", since = "1.0.0")]
+#![crate_type = "lib"]
+
+#[unstable(feature = "unstable_test_feature", issue = "0")] //~ WARN is deprecated
+pub fn zero() {}
+
+#[unstable(feature = "unstable_test_feature", issue = "none")]
+pub fn none() {}
+
+#[allow(unstable_issue_zero)]
+#[unstable(feature = "unstable_test_feature", issue = "0")]
+pub fn allowed_zero() {}
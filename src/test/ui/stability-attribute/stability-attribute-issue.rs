@@ -20,5 +20,5 @@ fn main() {
     unstable();
     //~^ ERROR use of unstable library feature 'unstable_test_feature' (see issue #1)
     unstable_msg();
-    //~^ ERROR use of unstable library feature 'unstable_test_feature': message (see issue #2)
+    //~^ ERROR use of unstable library feature 'unstable_test_feature' (see issue #2)
 }
@@ -54,7 +54,7 @@ mod bogus_attribute_types_2 {
 }
 
 mod missing_feature_names {
-    #[unstable(issue = "0")] //~ ERROR missing 'feature' [E0546]
+    #[unstable(issue = "none")] //~ ERROR missing 'feature' [E0546]
     fn f1() { }
 
     #[unstable(feature = "b")] //~ ERROR missing 'issue' [E0547]
@@ -73,12 +73,12 @@ mod missing_version {
     fn f2() { }
 }
 
-#[unstable(feature = "b", issue = "0")]
+#[unstable(feature = "b", issue = "none")]
 #[stable(feature = "a", since = "b")] //~ ERROR multiple stability levels [E0544]
 fn multiple1() { }
 
-#[unstable(feature = "b", issue = "0")]
-#[unstable(feature = "b", issue = "0")] //~ ERROR multiple stability levels [E0544]
+#[unstable(feature = "b", issue = "none")]
+#[unstable(feature = "b", issue = "none")] //~ ERROR multiple stability levels [E0544]
 fn multiple2() { }
 
 #[stable(feature = "a", since = "b")]
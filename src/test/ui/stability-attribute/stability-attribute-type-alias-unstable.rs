@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+
+#![feature(staged_api)]
+#![stable(feature = "stable_test_feature", since = "1.0.0")]
+
+#[unstable(feature = "unstable_test_feature", issue = "none")]
+pub struct Unstable;
+
+#[stable(feature = "stable_test_feature", since = "1.0.0")]
+pub type StableAlias = Unstable;
+//~^ WARN stable type alias `StableAlias` refers to unstable type `Unstable`
+
+#[stable(feature = "stable_test_feature", since = "1.0.0")]
+pub type StablePair = (u32, &'static Unstable);
+//~^ WARN stable type alias `StablePair` refers to unstable type `Unstable`
+
+#[stable(feature = "stable_test_feature", since = "1.0.0")]
+#[allow(unstable_type_in_stable_alias)]
+pub type AllowedAlias = Unstable;
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+
+// Stable type aliases may name stable or built-in types, and unstable
+// aliases may name anything.
+
+#![feature(staged_api)]
+#![stable(feature = "stable_test_feature", since = "1.0.0")]
+
+#[stable(feature = "stable_test_feature", since = "1.0.0")]
+pub struct Stable;
+
+#[unstable(feature = "unstable_test_feature", issue = "none")]
+pub struct Unstable;
+
+#[stable(feature = "stable_test_feature", since = "1.0.0")]
+pub type StableAlias = Stable;
+
+#[stable(feature = "stable_test_feature", since = "1.0.0")]
+pub type PrimAlias = u32;
+
+#[unstable(feature = "unstable_test_feature", issue = "none")]
+pub type UnstableAlias = Unstable;
+
+fn main() {}